@@ -0,0 +1,158 @@
+// JD Chain Contract Macros
+// #[contract] 为 impl 块中标注 #[method] 的方法生成合约导出方法
+// #[derive(ContractEvent)] 为结构实现类型化合约事件
+
+extern crate proc_macro;
+
+use proc_macro::TokenStream;
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use quote::quote;
+use syn::spanned::Spanned;
+use syn::{parse_macro_input, DeriveInput, Error, FnArg, ImplItem, ItemImpl, LitInt, LitStr, Pat, ReturnType, Signature, Type};
+
+// 标注合约实现，合约类型须实现 Default，每次调用导出方法时构建新的合约实例
+#[proc_macro_attribute]
+pub fn contract(attr: TokenStream, item: TokenStream) -> TokenStream {
+    if !attr.is_empty() {
+        return Error::new(TokenStream2::from(attr).span(), "#[contract] takes no arguments")
+            .to_compile_error()
+            .into();
+    }
+    let mut item_impl = parse_macro_input!(item as ItemImpl);
+    if let Some((_, path, _)) = &item_impl.trait_ {
+        return Error::new(path.span(), "#[contract] must be placed on an inherent impl block")
+            .to_compile_error()
+            .into();
+    }
+
+    let self_ty = item_impl.self_ty.clone();
+    let mut exports = Vec::new();
+    for impl_item in item_impl.items.iter_mut() {
+        if let ImplItem::Fn(method) = impl_item {
+            let len = method.attrs.len();
+            method.attrs.retain(|attr| !attr.path().is_ident("method"));
+            if method.attrs.len() != len {
+                exports.push(export(&self_ty, &method.sig).unwrap_or_else(Error::into_compile_error));
+            }
+        }
+    }
+
+    let expanded = quote! {
+        #item_impl
+        #(#exports)*
+    };
+    expanded.into()
+}
+
+// 标注合约导出方法，须在 #[contract] impl 块中使用
+// #[contract] 展开前会移除方法上的 #[method]，单独展开即为误用，报错并保留原方法以免产生后续错误
+#[proc_macro_attribute]
+pub fn method(_attr: TokenStream, item: TokenStream) -> TokenStream {
+    let item = TokenStream2::from(item);
+    let error = Error::new(Span::call_site(), "#[method] must be used inside a #[contract] impl block").to_compile_error();
+    let expanded = quote! {
+        #error
+        #item
+    };
+    expanded.into()
+}
+
+// 生成导出方法：解码参数，构建合约实例，调用方法并编码返回值
+fn export(self_ty: &Type, sig: &Signature) -> Result<TokenStream2, Error> {
+    if !sig.generics.params.is_empty() {
+        return Err(Error::new(sig.generics.span(), "contract methods cannot be generic"));
+    }
+    if sig.asyncness.is_some() {
+        return Err(Error::new(sig.asyncness.span(), "contract methods cannot be async"));
+    }
+
+    let name = &sig.ident;
+    let mut has_receiver = false;
+    let mut args = Vec::new();
+    let mut types = Vec::new();
+    for input in &sig.inputs {
+        match input {
+            FnArg::Receiver(_) => has_receiver = true,
+            FnArg::Typed(arg) => match &*arg.pat {
+                Pat::Ident(pat) => {
+                    args.push(pat.ident.clone());
+                    types.push((*arg.ty).clone());
+                }
+                pat => return Err(Error::new(pat.span(), "contract method arguments must be plain identifiers")),
+            },
+        }
+    }
+    let ret = match &sig.output {
+        ReturnType::Default => quote! { () },
+        ReturnType::Type(_, ty) => quote! { #ty },
+    };
+
+    let ffi = quote! { ::jdchain_rust_contract::jdcc_ffi };
+    let call = if has_receiver {
+        quote! {
+            let mut contract = <#self_ty as ::core::default::Default>::default();
+            contract.#name(#(#args),*)
+        }
+    } else {
+        quote! { <#self_ty>::#name(#(#args),*) }
+    };
+
+    Ok(quote! {
+        /// 合约导出方法，由运行时调用
+        ///
+        /// # Safety
+        /// 指针类参数须为运行时传入的以 NUL 结尾的字符串，且在调用期间有效
+        #[no_mangle]
+        pub unsafe extern "C" fn #name(#(#args: <#types as #ffi::FromAbi>::Abi),*) -> <#ret as #ffi::IntoAbi>::Abi {
+            #(
+                let #args = match unsafe { <#types as #ffi::FromAbi>::from_abi(#args) } {
+                    ::core::result::Result::Ok(val) => val,
+                    ::core::result::Result::Err(err) => #ffi::abort(err),
+                };
+            )*
+            let ret = { #call };
+            match #ffi::IntoAbi::into_abi(ret) {
+                ::core::result::Result::Ok(val) => val,
+                ::core::result::Result::Err(err) => #ffi::abort(err),
+            }
+        }
+    })
+}
+
+// 实现 ContractEvent，类型须同时实现 Serialize 与 Deserialize
+// #[event(topic = "...", version = N)] 指定主题及结构版本，默认主题为类型名，默认版本为 1
+#[proc_macro_derive(ContractEvent, attributes(event))]
+pub fn derive_contract_event(item: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(item as DeriveInput);
+    contract_event(&input).unwrap_or_else(Error::into_compile_error).into()
+}
+
+fn contract_event(input: &DeriveInput) -> Result<TokenStream2, Error> {
+    let mut topic = LitStr::new(&input.ident.to_string(), input.ident.span());
+    let mut version = 1u32;
+    for attr in input.attrs.iter().filter(|attr| attr.path().is_ident("event")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("topic") {
+                topic = meta.value()?.parse()?;
+                if topic.value().is_empty() {
+                    return Err(Error::new(topic.span(), "event topic cannot be empty"));
+                }
+            } else if meta.path.is_ident("version") {
+                version = meta.value()?.parse::<LitInt>()?.base10_parse()?;
+            } else {
+                return Err(meta.error("expected `topic` or `version`"));
+            }
+            Ok(())
+        })?;
+    }
+
+    let version = LitInt::new(&version.to_string(), input.ident.span());
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics ::jdchain_rust_contract::jdcc_event::ContractEvent for #name #ty_generics #where_clause {
+            const TOPIC: &'static str = #topic;
+            const VERSION: u32 = #version;
+        }
+    })
+}
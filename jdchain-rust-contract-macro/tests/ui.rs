@@ -0,0 +1,7 @@
+// 编译期错误信息校验，预期输出见 tests/ui/*.stderr
+
+#[test]
+fn ui() {
+    let t = trybuild::TestCases::new();
+    t.compile_fail("tests/ui/*.rs");
+}
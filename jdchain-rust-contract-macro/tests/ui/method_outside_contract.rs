@@ -0,0 +1,16 @@
+// #[method] 未在 #[contract] impl 块中使用
+
+use jdchain_rust_contract_macro::method;
+
+struct Contract;
+
+impl Contract {
+    #[method]
+    pub fn get(&self) -> i64 {
+        0
+    }
+}
+
+fn main() {
+    let _ = Contract.get();
+}
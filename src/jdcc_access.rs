@@ -0,0 +1,145 @@
+// JD Chain Contract Access
+// 合约所有者及角色成员记录于数据账户 KV，校验时要求所有者或角色成员参与交易签名
+// 所有者：access.owner；角色成员：access.role.{role}/{address}，成员数：access.role.{role}.len
+
+use serde::{Deserialize, Serialize};
+
+use crate::jdcc_api::{HostBackend, LedgerService};
+use crate::jdcc_auth::Auth;
+use crate::jdcc_event::ContractEvent;
+use crate::jdcc_ffi::WasmHost;
+use crate::jdcc_storage::{Storage, StorageSet};
+use crate::jdcc_types::*;
+
+const OWNER_KEY: &str = "access.owner";
+
+// 所有者变更事件
+#[derive(ContractEvent, Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[event(topic = "access.ownership_transferred")]
+pub struct OwnershipTransferred {
+    // 原所有者，初始化时为空
+    pub previous: Option<Address>,
+    pub owner: Address,
+}
+
+// 授予角色事件
+#[derive(ContractEvent, Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[event(topic = "access.role_granted")]
+pub struct RoleGranted {
+    pub role: String,
+    pub account: Address,
+}
+
+// 撤销角色事件
+#[derive(ContractEvent, Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[event(topic = "access.role_revoked")]
+pub struct RoleRevoked {
+    pub role: String,
+    pub account: Address,
+}
+
+// 所有者及角色访问控制
+pub struct Access<'a, B = WasmHost> {
+    storage: Storage<'a, B>,
+    auth: Auth<'a, B>,
+    // 变更事件发布的事件账户，为空时不发布
+    events: Option<Address>,
+}
+
+impl<'a, B: HostBackend> Access<'a, B> {
+    pub fn new(service: &'a LedgerService<B>, address: &Address) -> Self {
+        Access { storage: service.storage(address), auth: service.auth(), events: None }
+    }
+
+    // 变更时向事件账户发布事件
+    pub fn with_events(mut self, account: &Address) -> Self {
+        self.events = Some(account.clone());
+        self
+    }
+
+    pub fn auth(&self) -> &Auth<'a, B> {
+        &self.auth
+    }
+
+    pub fn owner(&self) -> Result<Option<Address>, ContractError> {
+        self.storage.get(OWNER_KEY)
+    }
+
+    // 设置初始所有者，须由该所有者签名，所有者已设置时返回错误
+    pub fn init_owner(&self, owner: &Address) -> Result<(), ContractError> {
+        if let Some(current) = self.owner()? {
+            return Err(ContractError::OwnerAlreadySet(current));
+        }
+        self.auth.require_signer(owner)?;
+        self.set_owner(None, owner)
+    }
+
+    // 要求所有者参与签名，返回所有者
+    pub fn only_owner(&self) -> Result<Address, ContractError> {
+        let owner = self.owner()?.ok_or(ContractError::OwnerNotSet)?;
+        self.auth.require_signer(&owner)?;
+        Ok(owner)
+    }
+
+    // 转移所有权，须由当前所有者签名
+    pub fn transfer_ownership(&self, owner: &Address) -> Result<(), ContractError> {
+        let previous = self.only_owner()?;
+        self.set_owner(Some(previous), owner)
+    }
+
+    pub fn has_role(&self, role: &str, account: &Address) -> Result<bool, ContractError> {
+        self.members(role).contains(account)
+    }
+
+    // 角色成员数
+    pub fn role_size(&self, role: &str) -> Result<u64, ContractError> {
+        self.members(role).len()
+    }
+
+    // 要求具有角色的用户参与签名，返回第一个具有角色的签名用户
+    pub fn only_role(&self, role: &str) -> Result<Address, ContractError> {
+        let members = self.members(role);
+        for signer in self.auth.signers()? {
+            if members.contains(&signer)? {
+                return Ok(signer);
+            }
+        }
+        Err(ContractError::MissingRole(role.to_string()))
+    }
+
+    // 授予角色，须由所有者签名，已具有角色时返回 false
+    pub fn grant_role(&self, role: &str, account: &Address) -> Result<bool, ContractError> {
+        self.only_owner()?;
+        if !self.members(role).insert(account)? {
+            return Ok(false);
+        }
+        self.emit(&RoleGranted { role: role.to_string(), account: account.clone() })?;
+        Ok(true)
+    }
+
+    // 撤销角色，须由所有者签名，不具有角色时返回 false
+    pub fn revoke_role(&self, role: &str, account: &Address) -> Result<bool, ContractError> {
+        self.only_owner()?;
+        if !self.members(role).remove(account)? {
+            return Ok(false);
+        }
+        self.emit(&RoleRevoked { role: role.to_string(), account: account.clone() })?;
+        Ok(true)
+    }
+
+    fn set_owner(&self, previous: Option<Address>, owner: &Address) -> Result<(), ContractError> {
+        self.storage.set(OWNER_KEY, owner)?;
+        self.emit(&OwnershipTransferred { previous, owner: owner.clone() })
+    }
+
+    fn members(&self, role: &str) -> StorageSet<'_, Address, B> {
+        self.storage.set_of(&format!("access.role.{}", role))
+    }
+
+    fn emit<E: ContractEvent>(&self, event: &E) -> Result<(), ContractError> {
+        if let Some(account) = &self.events {
+            self.storage.service().emit_event(account, event)?;
+        }
+        Ok(())
+    }
+}
@@ -1,330 +1,735 @@
 // JD Chain Contract API
 
-use std::ffi::{c_void, CStr, CString};
-use std::mem;
-use std::os::raw::c_char;
+use std::cell::RefCell;
+use std::rc::Rc;
 
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use crate::jdcc_access::Access;
+use crate::jdcc_auth::Auth;
+use crate::jdcc_event::ContractEvent;
+use crate::jdcc_ffi::WasmHost;
+use crate::jdcc_overlay::Overlay;
+use crate::jdcc_storage::Storage;
 use crate::jdcc_types::*;
 
-extern "C" {
-    pub fn sys_call(req_len: i32, req_ptr: *mut c_char) -> usize;
-    pub fn sys_msg(msg_len: i32, msg_ptr: *mut c_char) -> *mut c_char;
+// 宿主交互接口
+// 合约运行时默认使用 WasmHost，本地单元测试可注入自定义实现
+// 请求与返回均为 Message 编码后的数据
+pub trait HostBackend {
+    // 发送请求并读取宿主返回
+    fn call(&self, req: &[u8]) -> Result<Vec<u8>, ContractError>;
+
+    // 发送请求，不读取宿主返回
+    fn send(&self, req: &[u8]);
 }
 
-#[no_mangle]
-pub extern fn allocate(size: usize) -> *mut c_void {
-    let mut buffer = Vec::with_capacity(size);
-    let ptr = buffer.as_mut_ptr();
-    mem::forget(buffer);
+impl<B: HostBackend + ?Sized> HostBackend for &B {
+    fn call(&self, req: &[u8]) -> Result<Vec<u8>, ContractError> {
+        (**self).call(req)
+    }
 
-    ptr as *mut c_void
+    fn send(&self, req: &[u8]) {
+        (**self).send(req)
+    }
 }
 
-#[no_mangle]
-pub extern fn deallocate(ptr: *mut c_void, capacity: usize) {
-    unsafe {
-        let _ = Vec::from_raw_parts(ptr, 0, capacity);
-    }
+// 解析后的返回头部及二进制载荷
+type Reply<T> = (T, Option<Vec<u8>>);
+
+// 账本服务接口
+pub struct LedgerService<B = WasmHost> {
+    backend: B,
+    // KV 读缓存，启用后重复读取由缓存返回
+    overlay: Option<Rc<RefCell<Overlay>>>,
 }
 
-#[no_mangle]
-pub extern fn drop_string(ptr: *mut c_char) {
-    unsafe {
-        let _ = CString::from_raw(ptr);
+impl Default for LedgerService {
+    fn default() -> Self {
+        LedgerService::new(WasmHost::default())
     }
 }
 
-// 账本服务接口
-pub struct LedgerService {
-    logger: Logger,
+thread_local! {
+    // 当前合约调用的 KV 读缓存，由 before_event/post_event 等导出方法共享
+    static INVOCATION: Rc<RefCell<Overlay>> = Rc::new(RefCell::new(Overlay::default()));
 }
 
 impl LedgerService {
-    pub fn default() -> Self {
-        LedgerService {
-            logger: Logger {}
+    // 使用当前合约调用共享 KV 读缓存的账本服务
+    // 同一合约调用中各导出方法构建的实例共享缓存，须在 before_event/post_event 中 discard 清空
+    pub fn invocation() -> Self {
+        LedgerService { backend: WasmHost::default(), overlay: Some(INVOCATION.with(Rc::clone)) }
+    }
+}
+
+impl<B: HostBackend> LedgerService<B> {
+    // 使用指定宿主交互实现构建账本服务
+    pub fn new(backend: B) -> Self {
+        LedgerService { backend, overlay: None }
+    }
+
+    // 启用 KV 读缓存：重复读取由缓存返回，写入直接提交宿主并更新缓存
+    pub fn with_overlay(mut self) -> Self {
+        self.overlay = Some(Rc::new(RefCell::new(Overlay::default())));
+        self
+    }
+
+    // 丢弃缓存
+    pub fn discard(&self) {
+        if let Some(overlay) = &self.overlay {
+            overlay.borrow_mut().clear();
         }
     }
 
-    fn call_and_get_sys_msg(&self, req: &str) -> &str {
-        let req_len = req.len();
-        let msg_len;
-        unsafe {
-            msg_len = sys_call(req_len as i32, CString::new(req).unwrap().into_raw()) as usize;
-        };
-        let msg_ptr = allocate(msg_len) as *mut c_char;
-        let msg_ptr = unsafe {
-            sys_msg(msg_len as i32, msg_ptr)
-        };
-        let msg = unsafe { CStr::from_ptr(msg_ptr).to_str().unwrap() };
-        deallocate(msg_ptr as *mut c_void, msg_len);
+    pub fn backend(&self) -> &B {
+        &self.backend
+    }
+
+    // 发送请求，校验响应编码并解析返回头部及二进制载荷
+    fn exchange<T: DeserializeOwned>(&self, req: Message) -> Result<Reply<T>, ContractError> {
+        let ret = Message::decode(self.backend.call(&req.encode())?)?;
+        let response: Response = serde_json::from_str(&ret.header)?;
+        response.check()?;
+        Ok((serde_json::from_str(&ret.header)?, ret.payload))
+    }
+
+    // 发送请求，校验响应编码并解析返回
+    fn call<T: DeserializeOwned>(&self, req: String) -> Result<T, ContractError> {
+        Ok(self.exchange(Message::json(req))?.0)
+    }
+
+    // 查询账户，账户不存在时返回 None
+    fn query_account<T: DeserializeOwned>(&self, req: String) -> Result<Option<T>, ContractError> {
+        match self.call(req) {
+            Ok(val) => Ok(Some(val)),
+            Err(e) if e.code() == Some(ResultCode::NotFound) => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
+    // 查询账户数据，数据不存在时返回 None，所在账户不存在时返回错误
+    fn query<T: DeserializeOwned>(&self, req: String) -> Result<Option<Reply<T>>, ContractError> {
+        match self.exchange(Message::json(req)) {
+            Ok(val) => Ok(Some(val)),
+            Err(e) if e.code() == Some(ResultCode::NotFound) && e.sub_code() != Some(ErrorCode::AccountNotFound) => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
+    // 带版本写KV，宿主返回版本冲突时转换为 ContractError::VersionConflict
+    fn set_versioned(&self, key: &str, version: i64, req: Message) -> Result<i64, ContractError> {
+        match self.exchange::<SetKVResult>(req) {
+            Ok((result, _)) => required(result.ver, "ver"),
+            Err(e) if e.sub_code() == Some(ErrorCode::VersionConflict) => Err(ContractError::VersionConflict { key: key.to_string(), version }),
+            Err(e) => Err(e),
+        }
+    }
+
+    // 启用缓存时复制待写入的数据，未启用时返回 None
+    fn snapshot(&self, value: impl FnOnce() -> DataValue) -> Option<DataValue> {
+        self.overlay.as_ref().map(|_| value())
+    }
 
-        msg
+    // 记录写入结果：成功时缓存写入的数据及宿主返回的版本，失败时移除缓存数据
+    fn written(&self, address: &Address, key: &str, value: Option<DataValue>, ret: Result<i64, ContractError>) -> Result<i64, ContractError> {
+        if let (Some(overlay), Some(value)) = (&self.overlay, value) {
+            match &ret {
+                Ok(version) => overlay.borrow_mut().store(address, key, value, *version),
+                Err(_) => overlay.borrow_mut().invalidate(address, key),
+            }
+        }
+        ret
     }
 
-    pub fn logger(&self) -> &Logger {
-        &self.logger
+    pub fn logger(&self) -> Logger<'_, B> {
+        Logger { backend: &self.backend }
+    }
+
+    // 数据账户中记录的所有者及角色访问控制
+    pub fn access(&self, address: &Address) -> Access<'_, B> {
+        Access::new(self, address)
+    }
+
+    // 基于交易签名用户的授权校验
+    pub fn auth(&self) -> Auth<'_, B> {
+        Auth::new(self)
+    }
+
+    // 指定数据账户的类型化存储
+    pub fn storage(&self, address: &Address) -> Storage<'_, B> {
+        Storage::new(self, address)
     }
 
     // 获取账本哈希
-    pub fn get_ledger_hash(&self) -> Option<String> {
+    pub fn get_ledger_hash(&self) -> Result<HashDigest, ContractError> {
         let req = Request::get_ledger_hash();
-        let ret = self.call_and_get_sys_msg(&req);
-        let result: GetLedgerHashResult = match serde_json::from_str(ret) {
-            Ok(val) => val,
-            Err(_) => GetLedgerHashResult { rc: ERROR, lh: None },
-        };
-        match result.rc {
-            SUCCESS => result.lh,
-            _ => None
-        }
+        let result: GetLedgerHashResult = self.call(req)?;
+        required(result.lh, "lh")
     }
 
     // 获取合约地址
-    pub fn get_contract_address(&self) -> Option<String> {
+    pub fn get_contract_address(&self) -> Result<Address, ContractError> {
         let req = Request::get_contract_address();
-        let ret = self.call_and_get_sys_msg(&req);
-        let result: GetContractAddressResult = match serde_json::from_str(ret) {
-            Ok(val) => val,
-            Err(_) => GetContractAddressResult { rc: ERROR, ca: None },
-        };
-        match result.rc {
-            SUCCESS => result.ca,
-            _ => None
-        }
+        let result: GetContractAddressResult = self.call(req)?;
+        required(result.ca, "ca")
     }
 
     // 获取交易哈希
-    pub fn get_tx_hash(&self) -> Option<String> {
+    pub fn get_tx_hash(&self) -> Result<HashDigest, ContractError> {
         let req = Request::get_tx_hash();
-        let ret = self.call_and_get_sys_msg(&req);
-        let result: GetTxHashResult = match serde_json::from_str(ret) {
-            Ok(val) => val,
-            Err(_) => GetTxHashResult { rc: ERROR, th: None },
-        };
-        match result.rc {
-            SUCCESS => result.th,
-            _ => None
-        }
+        let result: GetTxHashResult = self.call(req)?;
+        required(result.th, "th")
     }
 
     // 获取交易时间
-    pub fn get_tx_time(&self) -> Option<u64> {
+    pub fn get_tx_time(&self) -> Result<u64, ContractError> {
         let req = Request::get_tx_time();
-        let ret = self.call_and_get_sys_msg(&req);
-        let result: GetTxTimeResult = match serde_json::from_str(ret) {
-            Ok(val) => val,
-            Err(_) => GetTxTimeResult { rc: ERROR, tt: None },
-        };
-        match result.rc {
-            SUCCESS => result.tt,
-            _ => None
-        }
+        let result: GetTxTimeResult = self.call(req)?;
+        required(result.tt, "tt")
     }
 
     // 获取交易签名用户地址列表
-    pub fn get_signers(&self) -> Option<Vec<String>> {
+    // 启用缓存时仅在首次调用时查询
+    pub fn get_signers(&self) -> Result<Vec<Address>, ContractError> {
+        if let Some(signers) = self.overlay.as_ref().and_then(|overlay| overlay.borrow().signers().map(<[Address]>::to_vec)) {
+            return Ok(signers);
+        }
         let req = Request::get_signers();
-        let ret = self.call_and_get_sys_msg(&req);
-        let result: GetSignersResult = match serde_json::from_str(ret) {
-            Ok(val) => val,
-            Err(_) => GetSignersResult { rc: ERROR, ss: None },
-        };
-        match result.rc {
-            SUCCESS => result.ss,
-            _ => None
+        let result: GetSignersResult = self.call(req)?;
+        let signers = required(result.ss, "ss")?;
+        if let Some(overlay) = &self.overlay {
+            overlay.borrow_mut().load_signers(&signers);
         }
+        Ok(signers)
     }
 
-    // 注册用户
-    pub fn register_user(&self, seed_ptr: *mut c_char) -> Option<String> {
-        let seed = unsafe { CStr::from_ptr(seed_ptr).to_str().unwrap() };
-        let req = Request::register_user(seed.to_string());
-        let ret = self.call_and_get_sys_msg(&req);
-        let result: RegisterUserResult = match serde_json::from_str(ret) {
-            Ok(val) => val,
-            Err(_) => RegisterUserResult { rc: ERROR, a: None },
-        };
-        match result.rc {
-            SUCCESS => result.a,
-            _ => None
-        }
+    // 获取最新区块高度
+    pub fn get_latest_block_height(&self) -> Result<i64, ContractError> {
+        let result: GetLatestBlockHeightResult = self.call(Request::get_latest_block_height())?;
+        required(result.h, "h")
+    }
+
+    // 获取最新区块哈希
+    pub fn get_latest_block_hash(&self) -> Result<HashDigest, ContractError> {
+        let result: GetLatestBlockHashResult = self.call(Request::get_latest_block_hash())?;
+        required(result.bh, "bh")
+    }
+
+    // 获取账本参与方列表
+    pub fn get_participants(&self) -> Result<Vec<Participant>, ContractError> {
+        let result: GetParticipantsResult = self.call(Request::get_participants())?;
+        required(result.ps, "ps")
+    }
+
+    // 获取参与共识的节点
+    pub fn get_consensus_nodes(&self) -> Result<Vec<Participant>, ContractError> {
+        let mut participants = self.get_participants()?;
+        participants.retain(|participant| participant.state == ParticipantState::Consensus);
+        Ok(participants)
     }
 
-    // 查询用户
-    pub fn get_user(&self, address_ptr: *mut c_char) -> Option<User> {
-        let address = unsafe { CStr::from_ptr(address_ptr).to_str().unwrap() };
+    // 获取账本配置
+    pub fn get_ledger_settings(&self) -> Result<LedgerSettings, ContractError> {
+        let result: GetLedgerSettingsResult = self.call(Request::get_ledger_settings())?;
+        Ok(LedgerSettings {
+            consensus_provider: required(result.cp, "cp")?,
+            hash_algorithm: required(result.ha, "ha")?,
+            crypto_algorithms: result.cas.unwrap_or_default(),
+            auto_verify_hash: result.vh.unwrap_or_default(),
+        })
+    }
+
+    // 注册用户，使用默认签名算法 ED25519
+    pub fn register_user(&self, seed: &str) -> Result<Address, ContractError> {
+        self.register_user_with(seed, Algorithm::default())
+    }
+
+    // 注册用户，使用指定签名算法
+    pub fn register_user_with(&self, seed: &str, algorithm: Algorithm) -> Result<Address, ContractError> {
+        let req = Request::register_user(seed.to_string(), algorithm);
+        let result: RegisterUserResult = self.call(req)?;
+        required(result.a, "a")
+    }
+
+    // 以公钥注册用户，私钥无需上链
+    pub fn register_user_with_pubkey(&self, pubkey: &PubKey) -> Result<Address, ContractError> {
+        let req = Request::register_user_with_pubkey(pubkey.to_string());
+        let result: RegisterUserResult = self.call(req)?;
+        required(result.a, "a")
+    }
+
+    // 查询用户，用户不存在时返回 None
+    pub fn get_user(&self, address: &Address) -> Result<Option<User>, ContractError> {
         let req = Request::get_user(address.to_string());
-        let ret = self.call_and_get_sys_msg(&req);
-        let result: GetUserResult = match serde_json::from_str(ret) {
-            Ok(val) => val,
-            Err(_) => GetUserResult { rc: ERROR, a: None, pk: None },
-        };
-        match result.rc {
-            SUCCESS => Some(User { address: result.a.unwrap(), pubkey: result.pk.unwrap() }),
-            _ => None
+        match self.query_account::<GetUserResult>(req)? {
+            Some(result) => Ok(Some(User { address: required(result.a, "a")?, pubkey: required(result.pk, "pk")? })),
+            None => Ok(None),
         }
     }
 
-    // 注册数据账户
-    pub fn register_data_account(&self, seed_ptr: *mut c_char) -> Option<String> {
-        let seed = unsafe { CStr::from_ptr(seed_ptr).to_str().unwrap() };
-        let req = Request::register_data_account(seed.to_string());
-        let ret = self.call_and_get_sys_msg(&req);
-        let result: RegisterDataAccountResult = match serde_json::from_str(ret) {
-            Ok(val) => val,
-            Err(_) => RegisterDataAccountResult { rc: ERROR, a: None },
-        };
-        match result.rc {
-            SUCCESS => result.a,
-            _ => None
-        }
+    // 注册数据账户，使用默认签名算法 ED25519
+    pub fn register_data_account(&self, seed: &str) -> Result<Address, ContractError> {
+        self.register_data_account_with(seed, Algorithm::default())
     }
 
-    // 查询数据账户
-    pub fn get_data_account(&self, address_ptr: *mut c_char) -> Option<DataAccount> {
-        let address = unsafe { CStr::from_ptr(address_ptr).to_str().unwrap() };
+    // 注册数据账户，使用指定签名算法
+    pub fn register_data_account_with(&self, seed: &str, algorithm: Algorithm) -> Result<Address, ContractError> {
+        let req = Request::register_data_account(seed.to_string(), algorithm);
+        let result: RegisterDataAccountResult = self.call(req)?;
+        required(result.a, "a")
+    }
+
+    // 以公钥注册数据账户，私钥无需上链
+    pub fn register_data_account_with_pubkey(&self, pubkey: &PubKey) -> Result<Address, ContractError> {
+        let req = Request::register_data_account_with_pubkey(pubkey.to_string());
+        let result: RegisterDataAccountResult = self.call(req)?;
+        required(result.a, "a")
+    }
+
+    // 查询数据账户，账户不存在时返回 None
+    pub fn get_data_account(&self, address: &Address) -> Result<Option<DataAccount>, ContractError> {
         let req = Request::get_data_account(address.to_string());
-        let ret = self.call_and_get_sys_msg(&req);
-        let result: GetDataAccountResult = match serde_json::from_str(ret) {
-            Ok(val) => val,
-            Err(_) => GetDataAccountResult { rc: ERROR, a: None, pk: None },
-        };
-        match result.rc {
-            SUCCESS => Some(DataAccount { address: result.a.unwrap(), pubkey: result.pk.unwrap() }),
-            _ => None
+        match self.query_account::<GetDataAccountResult>(req)? {
+            Some(result) => Ok(Some(DataAccount { address: required(result.a, "a")?, pubkey: required(result.pk, "pk")? })),
+            None => Ok(None),
         }
     }
 
     // 写KV，字符类型，不带版本
-    pub fn set_text(&self, addr_ptr: *mut c_char, key_ptr: *mut c_char, value_ptr: *mut c_char) -> Option<i64> {
-        let address = unsafe { CStr::from_ptr(addr_ptr).to_str().unwrap() };
-        let key = unsafe { CStr::from_ptr(key_ptr).to_str().unwrap() };
-        let value = unsafe { CStr::from_ptr(value_ptr).to_str().unwrap() };
+    pub fn set_text(&self, address: &Address, key: &str, value: &str) -> Result<i64, ContractError> {
+        let cached = self.snapshot(|| DataValue::text(value));
         let req = Request::set_text(address.to_string(), key.to_string(), value.to_string());
-        let ret = self.call_and_get_sys_msg(&req);
-        let result: SetKVResult = match serde_json::from_str(ret) {
-            Ok(val) => val,
-            Err(_) => SetKVResult { rc: ERROR, ver: None },
-        };
-        match result.rc {
-            SUCCESS => result.ver,
-            _ => None
-        }
+        let ret = self.call::<SetKVResult>(req).and_then(|result| required(result.ver, "ver"));
+        self.written(address, key, cached, ret)
     }
 
-    // 写KV，字符类型
-    pub fn set_text_with_version(&self, addr_ptr: *mut c_char, key_ptr: *mut c_char, value_ptr: *mut c_char, version: i64) -> Option<i64> {
-        let address = unsafe { CStr::from_ptr(addr_ptr).to_str().unwrap() };
-        let key = unsafe { CStr::from_ptr(key_ptr).to_str().unwrap() };
-        let value = unsafe { CStr::from_ptr(value_ptr).to_str().unwrap() };
+    // 写KV，字符类型，版本冲突时返回 ContractError::VersionConflict
+    pub fn set_text_with_version(&self, address: &Address, key: &str, value: &str, version: i64) -> Result<i64, ContractError> {
+        let cached = self.snapshot(|| DataValue::text(value));
         let req = Request::set_text_with_version(address.to_string(), key.to_string(), value.to_string(), version);
-        let ret = self.call_and_get_sys_msg(&req);
-        let result: SetKVResult = match serde_json::from_str(ret) {
-            Ok(val) => val,
-            Err(_) => SetKVResult { rc: ERROR, ver: None },
-        };
-        match result.rc {
-            SUCCESS => result.ver,
-            _ => None
-        }
+        let ret = self.set_versioned(key, version, Message::json(req));
+        self.written(address, key, cached, ret)
     }
 
     // 写KV，数值类型，不带版本
-    pub fn set_int64(&self, addr_ptr: *mut c_char, key_ptr: *mut c_char, value: i64) -> Option<i64> {
-        let address = unsafe { CStr::from_ptr(addr_ptr).to_str().unwrap() };
-        let key = unsafe { CStr::from_ptr(key_ptr).to_str().unwrap() };
+    pub fn set_int64(&self, address: &Address, key: &str, value: i64) -> Result<i64, ContractError> {
         let req = Request::set_int64(address.to_string(), key.to_string(), value);
-        let ret = self.call_and_get_sys_msg(&req);
-        let result: SetKVResult = match serde_json::from_str(ret) {
-            Ok(val) => val,
-            Err(_) => SetKVResult { rc: ERROR, ver: None },
-        };
-        match result.rc {
-            SUCCESS => result.ver,
-            _ => None
-        }
+        let ret = self.call::<SetKVResult>(req).and_then(|result| required(result.ver, "ver"));
+        self.written(address, key, self.snapshot(|| DataValue::int64(value)), ret)
     }
 
-    // 写KV，数值类型
-    pub fn set_int64_with_version(&self, addr_ptr: *mut c_char, key_ptr: *mut c_char, value: i64, version: i64) -> Option<i64> {
-        let address = unsafe { CStr::from_ptr(addr_ptr).to_str().unwrap() };
-        let key = unsafe { CStr::from_ptr(key_ptr).to_str().unwrap() };
+    // 写KV，数值类型，版本冲突时返回 ContractError::VersionConflict
+    pub fn set_int64_with_version(&self, address: &Address, key: &str, value: i64, version: i64) -> Result<i64, ContractError> {
         let req = Request::set_int64_with_version(address.to_string(), key.to_string(), value, version);
-        let ret = self.call_and_get_sys_msg(&req);
-        let result: SetKVResult = match serde_json::from_str(ret) {
-            Ok(val) => val,
-            Err(_) => SetKVResult { rc: ERROR, ver: None },
-        };
-        match result.rc {
-            SUCCESS => result.ver,
-            _ => None
-        }
+        let ret = self.set_versioned(key, version, Message::json(req));
+        self.written(address, key, self.snapshot(|| DataValue::int64(value)), ret)
+    }
+
+    // 写KV，字节类型，不带版本
+    pub fn set_bytes(&self, address: &Address, key: &str, value: &[u8]) -> Result<i64, ContractError> {
+        let req = Request::set_bytes(address.to_string(), key.to_string());
+        let ret = self.exchange::<SetKVResult>(Message::with_payload(req, value.to_vec())).and_then(|(result, _)| required(result.ver, "ver"));
+        self.written(address, key, self.snapshot(|| DataValue::bytes(value)), ret)
+    }
+
+    // 写KV，字节类型，版本冲突时返回 ContractError::VersionConflict
+    pub fn set_bytes_with_version(&self, address: &Address, key: &str, value: &[u8], version: i64) -> Result<i64, ContractError> {
+        let req = Request::set_bytes_with_version(address.to_string(), key.to_string(), version);
+        let ret = self.set_versioned(key, version, Message::with_payload(req, value.to_vec()));
+        self.written(address, key, self.snapshot(|| DataValue::bytes(value)), ret)
+    }
+
+    // 写KV，任意数据类型，不带版本
+    pub fn set_value(&self, address: &Address, key: &str, value: DataValue) -> Result<i64, ContractError> {
+        let cached = self.snapshot(|| value.clone());
+        let (value_type, text, payload) = value.into_wire();
+        let req = Request::set_value(address.to_string(), key.to_string(), value_type, text);
+        let ret = self.exchange::<SetKVResult>(Message { header: req, payload }).and_then(|(result, _)| required(result.ver, "ver"));
+        self.written(address, key, cached, ret)
+    }
+
+    // 写KV，任意数据类型，版本冲突时返回 ContractError::VersionConflict
+    pub fn set_value_with_version(&self, address: &Address, key: &str, value: DataValue, version: i64) -> Result<i64, ContractError> {
+        let cached = self.snapshot(|| value.clone());
+        let (value_type, text, payload) = value.into_wire();
+        let req = Request::set_value_with_version(address.to_string(), key.to_string(), value_type, text, version);
+        let ret = self.set_versioned(key, version, Message { header: req, payload });
+        self.written(address, key, cached, ret)
+    }
+
+    // 写KV，JSON 类型，不带版本
+    pub fn set_json<T: Serialize + ?Sized>(&self, address: &Address, key: &str, value: &T) -> Result<i64, ContractError> {
+        self.set_value(address, key, DataValue::json(value)?)
     }
 
-    // 查询数据版本
-    pub fn get_value_version(&self, addr_ptr: *mut c_char, key_ptr: *mut c_char) -> Option<i64> {
-        let address = unsafe { CStr::from_ptr(addr_ptr).to_str().unwrap() };
-        let key = unsafe { CStr::from_ptr(key_ptr).to_str().unwrap() };
+    // 写KV，XML 类型，不带版本
+    pub fn set_xml(&self, address: &Address, key: &str, value: &str) -> Result<i64, ContractError> {
+        self.set_value(address, key, DataValue::xml(value))
+    }
+
+    // 写KV，时间戳类型，不带版本
+    pub fn set_timestamp(&self, address: &Address, key: &str, millis: i64) -> Result<i64, ContractError> {
+        self.set_value(address, key, DataValue::timestamp(millis))
+    }
+
+    // 写KV，图片类型，不带版本
+    pub fn set_image(&self, address: &Address, key: &str, value: &[u8]) -> Result<i64, ContractError> {
+        self.set_value(address, key, DataValue::image(value))
+    }
+
+    // 写KV，大整数类型，不带版本
+    pub fn set_big_int(&self, address: &Address, key: &str, value: &str) -> Result<i64, ContractError> {
+        self.set_value(address, key, DataValue::big_int(value)?)
+    }
+
+    // 查询数据版本，数据不存在时返回 -1
+    pub fn get_value_version(&self, address: &Address, key: &str) -> Result<i64, ContractError> {
+        if let Some(version) = self.overlay.as_ref().and_then(|overlay| overlay.borrow().version(address, key)) {
+            return Ok(version);
+        }
         let req = Request::get_value_version(address.to_string(), key.to_string());
-        let ret = self.call_and_get_sys_msg(&req);
-        let result: GetValueVersionResult = match serde_json::from_str(ret) {
-            Ok(val) => val,
-            Err(_) => GetValueVersionResult { rc: ERROR, ver: None },
+        let version = match self.query::<GetValueVersionResult>(req)? {
+            Some((result, _)) => required(result.ver, "ver")?,
+            None => -1,
         };
-        match result.rc {
-            SUCCESS => result.ver,
-            _ => None
+        if let Some(overlay) = &self.overlay {
+            overlay.borrow_mut().load_version(address, key, version);
         }
+        Ok(version)
     }
 
-    // 查询数据
-    pub fn get_value(&self, addr_ptr: *mut c_char, key_ptr: *mut c_char, version: i64) -> Option<KVData> {
-        let address = unsafe { CStr::from_ptr(addr_ptr).to_str().unwrap() };
-        let key = unsafe { CStr::from_ptr(key_ptr).to_str().unwrap() };
+    // 查询数据，数据不存在时返回 None
+    pub fn get_value(&self, address: &Address, key: &str, version: i64) -> Result<Option<KVData>, ContractError> {
+        if let Some(data) = self.overlay.as_ref().and_then(|overlay| overlay.borrow().read(address, key, version)) {
+            return Ok(data);
+        }
         let req = Request::get_value(address.to_string(), key.to_string(), version);
-        let ret = self.call_and_get_sys_msg(&req);
-        let result: GetValueResult = match serde_json::from_str(ret) {
-            Ok(val) => val,
-            Err(_) => GetValueResult { rc: ERROR, k: None, v: None, t: None, ver: None },
-        };
-        match result.rc {
-            SUCCESS => Some(KVData {
-                key: result.k.unwrap(),
-                value: result.v.unwrap(),
-                value_type: result.t.unwrap(),
-                version: result.ver.unwrap(),
+        let data = match self.query::<GetValueResult>(req)? {
+            Some((result, payload)) => Some(KVData {
+                key: required(result.k, "k")?,
+                value: DataValue::from_wire(required(result.t, "t")?, result.v, payload)?,
+                version: required(result.ver, "ver")?,
             }),
-            _ => None
+            None => None,
+        };
+        if let Some(overlay) = self.overlay.as_ref().filter(|_| version < 0) {
+            overlay.borrow_mut().load(address, key, data.as_ref());
+        }
+        Ok(data)
+    }
+
+    // 读取最新数据及版本，由 f 计算新值后按读取时的版本写入，返回写入后的版本
+    // 数据不存在时 f 的参数为 None；读写之间数据被修改时返回 ContractError::VersionConflict
+    pub fn update_value<F>(&self, address: &Address, key: &str, f: F) -> Result<i64, ContractError>
+    where
+        F: FnOnce(Option<DataValue>) -> Result<DataValue, ContractError>,
+    {
+        let (old, version) = match self.get_value(address, key, -1)? {
+            Some(data) => (Some(data.value), data.version),
+            None => (None, -1),
+        };
+        self.set_value_with_version(address, key, f(old)?, version)
+    }
+
+    // 读-改-写字符类型数据，原数据非字符类型时返回错误
+    pub fn update_text<F>(&self, address: &Address, key: &str, f: F) -> Result<i64, ContractError>
+    where
+        F: FnOnce(Option<&str>) -> String,
+    {
+        self.update_value(address, key, |old| match old {
+            None => Ok(DataValue::Text(f(None))),
+            Some(DataValue::Text(old)) => Ok(DataValue::Text(f(Some(&old)))),
+            Some(other) => Err(ContractError::Decode(format!("expect {:?} value but got {:?}", ValueType::Text, other.value_type()))),
+        })
+    }
+
+    // 读-改-写数值类型数据，原数据非数值类型时返回错误
+    pub fn update_int64<F>(&self, address: &Address, key: &str, f: F) -> Result<i64, ContractError>
+    where
+        F: FnOnce(Option<i64>) -> i64,
+    {
+        self.update_value(address, key, |old| match old {
+            None => Ok(DataValue::Int64(f(None))),
+            Some(DataValue::Int64(old)) => Ok(DataValue::Int64(f(Some(old)))),
+            Some(other) => Err(ContractError::Decode(format!("expect {:?} value but got {:?}", ValueType::Int64, other.value_type()))),
+        })
+    }
+
+    // 查询指定类型数据，数据不存在时返回 None，数据类型不符时返回错误
+    fn get_typed<T, F>(&self, address: &Address, key: &str, version: i64, value_type: ValueType, f: F) -> Result<Option<T>, ContractError>
+    where
+        F: FnOnce(DataValue) -> Option<T>,
+    {
+        match self.get_value(address, key, version)? {
+            Some(data) => {
+                let actual = data.value.value_type();
+                f(data.value).map(Some).ok_or_else(|| ContractError::Decode(format!("expect {:?} value but got {:?}", value_type, actual)))
+            }
+            None => Ok(None),
+        }
+    }
+
+    // 查询字符类型数据，数据不存在时返回 None，数据非字符类型时返回错误
+    pub fn get_text(&self, address: &Address, key: &str, version: i64) -> Result<Option<String>, ContractError> {
+        self.get_typed(address, key, version, ValueType::Text, |value| match value {
+            DataValue::Text(v) => Some(v),
+            _ => None,
+        })
+    }
+
+    // 查询数值类型数据，数据不存在时返回 None，数据非数值类型时返回错误
+    pub fn get_int64(&self, address: &Address, key: &str, version: i64) -> Result<Option<i64>, ContractError> {
+        self.get_typed(address, key, version, ValueType::Int64, |value| match value {
+            DataValue::Int64(v) => Some(v),
+            _ => None,
+        })
+    }
+
+    // 查询 XML 类型数据，数据不存在时返回 None，数据非 XML 类型时返回错误
+    pub fn get_xml(&self, address: &Address, key: &str, version: i64) -> Result<Option<String>, ContractError> {
+        self.get_typed(address, key, version, ValueType::Xml, |value| match value {
+            DataValue::Xml(v) => Some(v),
+            _ => None,
+        })
+    }
+
+    // 查询时间戳类型数据（毫秒），数据不存在时返回 None，数据非时间戳类型时返回错误
+    pub fn get_timestamp(&self, address: &Address, key: &str, version: i64) -> Result<Option<i64>, ContractError> {
+        self.get_typed(address, key, version, ValueType::Timestamp, |value| match value {
+            DataValue::Timestamp(v) => Some(v),
+            _ => None,
+        })
+    }
+
+    // 查询图片类型数据，数据不存在时返回 None，数据非图片类型时返回错误
+    pub fn get_image(&self, address: &Address, key: &str, version: i64) -> Result<Option<Vec<u8>>, ContractError> {
+        self.get_typed(address, key, version, ValueType::Image, |value| match value {
+            DataValue::Image(v) => Some(v),
+            _ => None,
+        })
+    }
+
+    // 查询大整数类型数据（十进制文本），数据不存在时返回 None，数据非大整数类型时返回错误
+    pub fn get_big_int(&self, address: &Address, key: &str, version: i64) -> Result<Option<String>, ContractError> {
+        self.get_typed(address, key, version, ValueType::BigInt, |value| match value {
+            DataValue::BigInt(v) => Some(v),
+            _ => None,
+        })
+    }
+
+    // 查询 JSON 类型数据并反序列化，数据不存在时返回 None，数据非 JSON 类型时返回错误
+    pub fn get_json<T: DeserializeOwned>(&self, address: &Address, key: &str, version: i64) -> Result<Option<T>, ContractError> {
+        match self.get_value(address, key, version)? {
+            Some(data) => Ok(Some(data.value.to_json()?)),
+            None => Ok(None),
+        }
+    }
+
+    // 查询字节类型数据，数据不存在时返回 None，数据非字节类型时返回错误
+    pub fn get_bytes(&self, address: &Address, key: &str, version: i64) -> Result<Option<BytesData>, ContractError> {
+        match self.get_value(address, key, version)? {
+            Some(KVData { key, value: DataValue::Bytes(value), version }) => Ok(Some(BytesData { key, value, version })),
+            Some(data) => Err(ContractError::Decode(format!("expect {:?} value but got {:?}", ValueType::Bytes, data.value.value_type()))),
+            None => Ok(None),
+        }
+    }
+
+    // 分页查询数据账户中各 key 的最新版本数据，按 key 首次写入顺序
+    pub fn get_data_entries(&self, address: &Address, from_index: u64, count: u64) -> Result<Vec<KVData>, ContractError> {
+        let req = Request::get_data_entries(address.to_string(), from_index, count);
+        let (result, payload) = self.exchange::<GetDataEntriesResult>(Message::json(req))?;
+        let payload = payload.unwrap_or_default();
+        let mut offset = 0;
+        let mut entries = Vec::new();
+        for entry in required(result.es, "es")? {
+            let bytes = slice_payload(&payload, &mut offset, entry.l)?;
+            entries.push(KVData { key: entry.k, value: DataValue::from_wire(entry.t, entry.v, bytes)?, version: entry.ver });
+        }
+        Ok(entries)
+    }
+
+    // 查询数据账户中的 key 总数
+    pub fn get_data_entries_total_count(&self, address: &Address) -> Result<u64, ContractError> {
+        let result: GetDataEntriesTotalCountResult = self.call(Request::get_data_entries_total_count(address.to_string()))?;
+        required(result.tc, "tc")
+    }
+
+    // 按页逐个读取数据账户中的 KV，遍历至某页不足 page_size 条时结束
+    pub fn data_entries(&self, address: &Address, page_size: u64) -> DataEntries<'_, B> {
+        DataEntries { service: self, address: address.clone(), page_size: page_size.max(1), next: 0, page: Vec::new().into_iter(), done: false }
+    }
+
+    // 注册事件账户，使用默认签名算法 ED25519
+    pub fn register_event_account(&self, seed: &str) -> Result<Address, ContractError> {
+        let req = Request::register_event_account(seed.to_string(), Algorithm::default());
+        let result: RegisterEventAccountResult = self.call(req)?;
+        required(result.a, "a")
+    }
+
+    // 发布事件，sequence 为该主题当前最新序号，主题下无事件时为 -1，返回发布后的序号
+    // 序号不一致时返回 ContractError::VersionConflict
+    pub fn publish_event(&self, account: &Address, topic: &str, content: DataValue, sequence: i64) -> Result<i64, ContractError> {
+        let (content_type, text, payload) = content.into_wire();
+        let req = Request::publish_event(account.to_string(), topic.to_string(), content_type, text, sequence);
+        match self.exchange::<PublishEventResult>(Message { header: req, payload }) {
+            Ok((result, _)) => required(result.seq, "seq"),
+            Err(e) if e.sub_code() == Some(ErrorCode::VersionConflict) => Err(ContractError::VersionConflict { key: topic.to_string(), version: sequence }),
+            Err(e) => Err(e),
+        }
+    }
+
+    // 查询主题下的最新事件，主题下无事件时返回 None，事件账户不存在时返回错误
+    pub fn get_latest_event(&self, account: &Address, topic: &str) -> Result<Option<Event>, ContractError> {
+        let req = Request::get_latest_event(account.to_string(), topic.to_string());
+        match self.query::<GetLatestEventResult>(req)? {
+            Some((result, payload)) => Ok(Some(Event {
+                account: account.clone(),
+                topic: required(result.tp, "tp")?,
+                content: DataValue::from_wire(required(result.t, "t")?, result.v, payload)?,
+                sequence: required(result.seq, "seq")?,
+                tx_hash: required(result.th, "th")?,
+            })),
+            None => Ok(None),
+        }
+    }
+
+    // 在最新序号之后发布类型化事件，返回发布后的序号
+    pub fn emit_event<E: ContractEvent>(&self, account: &Address, event: &E) -> Result<i64, ContractError> {
+        let content = event.encode()?;
+        let sequence = self.get_latest_event(account, E::TOPIC)?.map_or(-1, |latest| latest.sequence);
+        self.publish_event(account, E::TOPIC, content, sequence)
+    }
+
+    // 查询并解码主题下的最新类型化事件
+    pub fn get_latest_contract_event<E: ContractEvent>(&self, account: &Address) -> Result<Option<E>, ContractError> {
+        match self.get_latest_event(account, E::TOPIC)? {
+            Some(event) => E::from_event(&event).map(Some),
+            None => Ok(None),
+        }
+    }
+
+    // 调用合约方法，参数按数据类型编码
+    // 启用缓存时调用后清空缓存，被调合约的写入对后续读取可见
+    pub fn invoke_contract(&self, address: &Address, method: &str, args: &[DataValue]) -> Result<ReturnValue, ContractError> {
+        let (args, payload) = InvokeArg::encode(args.to_vec());
+        let req = Request::invoke_contract(address.to_string(), method.to_string(), args);
+        let ret = self.exchange::<InvokeContractResult>(Message { header: req, payload });
+        self.discard();
+        let (result, payload) = ret?;
+        match result.t {
+            Some(value_type) => Ok(ReturnValue::new(Some(DataValue::from_wire(value_type, result.v, payload)?))),
+            None => Ok(ReturnValue::new(None)),
+        }
+    }
+
+    // 查询角色权限，角色不存在时返回 None
+    pub fn get_role_privilege(&self, role: &str) -> Result<Option<RolePrivilege>, ContractError> {
+        match self.query::<GetRolePrivilegeResult>(Request::get_role_privilege(role.to_string()))? {
+            Some((result, _)) => Ok(Some(RolePrivilege {
+                role: required(result.r, "r")?,
+                ledger_permissions: result.lp.unwrap_or_default(),
+                transaction_permissions: result.tp.unwrap_or_default(),
+            })),
+            None => Ok(None),
+        }
+    }
+
+    // 查询用户角色，用户不存在时返回错误
+    pub fn get_user_roles(&self, address: &Address) -> Result<UserRoles, ContractError> {
+        let result: GetUserRolesResult = self.call(Request::get_user_roles(address.to_string()))?;
+        Ok(UserRoles { address: required(result.a, "a")?, roles: result.rs.unwrap_or_default(), policy: result.p.unwrap_or_default() })
+    }
+
+    // 查询用户权限，用户不存在时返回错误
+    pub fn get_user_privilege(&self, address: &Address) -> Result<UserPrivilege, ContractError> {
+        let result: GetUserPrivilegeResult = self.call(Request::get_user_privilege(address.to_string()))?;
+        Ok(UserPrivilege {
+            address: required(result.a, "a")?,
+            roles: result.rs.unwrap_or_default(),
+            ledger_permissions: result.lp.unwrap_or_default(),
+            transaction_permissions: result.tp.unwrap_or_default(),
+        })
+    }
+
+    // 配置角色权限，角色不存在时创建，须由具有 CONFIGURE_ROLES 权限的用户签名
+    pub fn configure_role(&self, config: &RoleConfig) -> Result<(), ContractError> {
+        let _: Response = self.call(Request::configure_role(config))?;
+        Ok(())
+    }
+
+    // 创建角色并启用指定权限
+    pub fn create_role(&self, role: &str, ledger: &[LedgerPermission], transaction: &[TransactionPermission]) -> Result<(), ContractError> {
+        self.configure_role(&RoleConfig::new(role).enable(ledger).enable_transaction(transaction))
+    }
+
+    // 授予用户角色并设置多角色权限合并策略，须由具有 AUTHORIZE_USER_ROLES 权限的用户签名
+    pub fn authorize_user(&self, address: &Address, roles: &[&str], policy: RolesPolicy) -> Result<(), ContractError> {
+        let roles = roles.iter().map(|role| role.to_string()).collect();
+        let _: Response = self.call(Request::authorize_user_roles(address.to_string(), roles, Vec::new(), Some(policy)))?;
+        Ok(())
+    }
+
+    // 撤销用户角色，合并策略不变
+    pub fn unauthorize_user(&self, address: &Address, roles: &[&str]) -> Result<(), ContractError> {
+        let roles = roles.iter().map(|role| role.to_string()).collect();
+        let _: Response = self.call(Request::authorize_user_roles(address.to_string(), Vec::new(), roles, None))?;
+        Ok(())
+    }
+}
+
+// 数据账户 KV 分页迭代器，读取完当前页后再查询下一页，查询出错时返回错误并结束
+pub struct DataEntries<'a, B = WasmHost> {
+    service: &'a LedgerService<B>,
+    address: Address,
+    page_size: u64,
+    // 下一页起始序号
+    next: u64,
+    page: std::vec::IntoIter<KVData>,
+    done: bool,
+}
+
+impl<B: HostBackend> Iterator for DataEntries<'_, B> {
+    type Item = Result<KVData, ContractError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(entry) = self.page.next() {
+            return Some(Ok(entry));
+        }
+        if self.done {
+            return None;
+        }
+        match self.service.get_data_entries(&self.address, self.next, self.page_size) {
+            Ok(page) => {
+                self.done = (page.len() as u64) < self.page_size;
+                self.next += page.len() as u64;
+                self.page = page.into_iter();
+                self.page.next().map(Ok)
+            }
+            Err(e) => {
+                self.done = true;
+                Some(Err(e))
+            }
         }
     }
 }
 
 // 日志接口
-pub struct Logger {}
+pub struct Logger<'a, B = WasmHost> {
+    backend: &'a B,
+}
 
-impl Logger {
+impl<B: HostBackend> Logger<'_, B> {
     pub fn debug(&self, msg: String) {
-        let data = Request::log_debug(msg);
-        let data_len = data.len() as i32;
-        unsafe {
-            sys_call(data_len, CString::new(data).unwrap().into_raw());
-        }
+        self.backend.send(Request::log_debug(msg).as_bytes());
     }
 
     pub fn info(&self, msg: String) {
-        let data = Request::log_info(msg);
-        let data_len = data.len() as i32;
-        unsafe {
-            sys_call(data_len, CString::new(data).unwrap().into_raw());
-        }
+        self.backend.send(Request::log_info(msg).as_bytes());
     }
 
     pub fn error(&self, msg: String) {
-        let data = Request::log_error(msg);
-        let data_len = data.len() as i32;
-        unsafe {
-            sys_call(data_len, CString::new(data).unwrap().into_raw());
-        }
+        self.backend.send(Request::log_error(msg).as_bytes());
     }
-}
\ No newline at end of file
+}
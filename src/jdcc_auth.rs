@@ -0,0 +1,69 @@
+// JD Chain Contract Auth
+// 基于交易签名用户的授权校验，签名用户列表在首次校验时查询并缓存
+
+use std::cell::RefCell;
+
+use crate::jdcc_api::{HostBackend, LedgerService};
+use crate::jdcc_ffi::WasmHost;
+use crate::jdcc_types::*;
+
+// 签名授权校验
+pub struct Auth<'a, B = WasmHost> {
+    service: &'a LedgerService<B>,
+    signers: RefCell<Option<Vec<Address>>>,
+}
+
+impl<'a, B: HostBackend> Auth<'a, B> {
+    pub fn new(service: &'a LedgerService<B>) -> Self {
+        Auth { service, signers: RefCell::new(None) }
+    }
+
+    // 交易签名用户地址列表
+    pub fn signers(&self) -> Result<Vec<Address>, ContractError> {
+        self.with_signers(<[Address]>::to_vec)
+    }
+
+    pub fn is_signer(&self, address: &Address) -> Result<bool, ContractError> {
+        self.with_signers(|signers| signers.contains(address))
+    }
+
+    // 要求指定地址参与签名
+    pub fn require_signer(&self, address: &Address) -> Result<(), ContractError> {
+        if self.is_signer(address)? {
+            Ok(())
+        } else {
+            Err(ContractError::MissingSigner(address.clone()))
+        }
+    }
+
+    // 要求指定地址中至少一个参与签名，返回第一个参与签名的地址
+    pub fn require_any_signer(&self, addresses: &[Address]) -> Result<Address, ContractError> {
+        self.with_signers(|signers| addresses.iter().find(|address| signers.contains(address)).cloned())?
+            .ok_or(ContractError::InsufficientSigners { required: 1, signed: 0 })
+    }
+
+    // 要求指定地址中至少 threshold 个参与签名，重复地址只计一次，返回参与签名的地址
+    pub fn require_threshold(&self, addresses: &[Address], threshold: usize) -> Result<Vec<Address>, ContractError> {
+        let signed = self.with_signers(|signers| {
+            let mut signed: Vec<Address> = Vec::new();
+            for address in addresses {
+                if signers.contains(address) && !signed.contains(address) {
+                    signed.push(address.clone());
+                }
+            }
+            signed
+        })?;
+        if signed.len() < threshold {
+            return Err(ContractError::InsufficientSigners { required: threshold, signed: signed.len() });
+        }
+        Ok(signed)
+    }
+
+    fn with_signers<R>(&self, f: impl FnOnce(&[Address]) -> R) -> Result<R, ContractError> {
+        if self.signers.borrow().is_none() {
+            let signers = self.service.get_signers()?;
+            *self.signers.borrow_mut() = Some(signers);
+        }
+        Ok(f(self.signers.borrow().as_deref().unwrap_or_default()))
+    }
+}
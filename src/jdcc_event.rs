@@ -0,0 +1,57 @@
+// JD Chain Contract Event
+// 类型化合约事件，发布方与消费方共用同一结构定义
+// 事件内容以 JSON 写入：{"ver": 结构版本, "data": 事件数据}
+
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+
+use crate::jdcc_types::*;
+
+pub use jdchain_rust_contract_macro::ContractEvent;
+
+// 类型化合约事件，通常由 #[derive(ContractEvent)] 实现
+pub trait ContractEvent: Serialize + DeserializeOwned {
+    // 事件主题，发布后不应修改
+    const TOPIC: &'static str;
+    // 结构版本，结构变更时递增
+    const VERSION: u32;
+
+    // 编码为事件内容
+    fn encode(&self) -> Result<DataValue, ContractError> {
+        DataValue::json(&Envelope { ver: Self::VERSION, data: self })
+    }
+
+    // 从事件内容解码，内容版本高于 VERSION 时返回错误
+    // 低版本内容按当前结构解码，新增字段须声明 #[serde(default)]
+    fn decode(content: &DataValue) -> Result<Self, ContractError> {
+        let text = match content {
+            DataValue::Json(v) | DataValue::Text(v) => v,
+            other => return Err(ContractError::Decode(format!("unsupported event content type {:?}", other.value_type()))),
+        };
+        let envelope: Envelope<Self> = serde_json::from_str(text)?;
+        if envelope.ver > Self::VERSION {
+            return Err(ContractError::Decode(format!(
+                "event {} version {} is newer than supported version {}",
+                Self::TOPIC,
+                envelope.ver,
+                Self::VERSION
+            )));
+        }
+        Ok(envelope.data)
+    }
+
+    // 从链上事件解码，主题不一致时返回错误
+    fn from_event(event: &Event) -> Result<Self, ContractError> {
+        if event.topic != Self::TOPIC {
+            return Err(ContractError::Decode(format!("expected event topic {}, got {}", Self::TOPIC, event.topic)));
+        }
+        Self::decode(&event.content)
+    }
+}
+
+// 事件内容
+#[derive(Serialize, Deserialize)]
+struct Envelope<T> {
+    ver: u32,
+    data: T,
+}
@@ -0,0 +1,245 @@
+// JD Chain Contract FFI
+// 所有原始指针处理集中于此：宿主导入函数、内存分配导出函数、合约导出方法参数与返回值编解码
+
+use std::convert::TryFrom;
+use std::ffi::{c_void, CStr, CString};
+use std::mem;
+use std::os::raw::c_char;
+
+use crate::jdcc_api::HostBackend;
+use crate::jdcc_types::{Address, Algorithm, ContractError, HashDigest, PubKey};
+
+extern "C" {
+    // 请求数据仅在调用期间有效，宿主须在返回前完成读取
+    pub fn sys_call(req_len: i32, req_ptr: *const c_char) -> usize;
+    pub fn sys_msg(msg_len: i32, msg_ptr: *mut c_char) -> *mut c_char;
+}
+
+// 宿主导入函数类型
+pub type SysCallFn = unsafe extern "C" fn(req_len: i32, req_ptr: *const c_char) -> usize;
+pub type SysMsgFn = unsafe extern "C" fn(msg_len: i32, msg_ptr: *mut c_char) -> *mut c_char;
+
+#[no_mangle]
+pub extern "C" fn allocate(size: usize) -> *mut c_void {
+    let mut buffer = Vec::with_capacity(size);
+    let ptr = buffer.as_mut_ptr();
+    mem::forget(buffer);
+
+    ptr
+}
+
+/// 释放 allocate 分配的内存
+///
+/// # Safety
+/// ptr 须由 allocate 分配且 capacity 与分配时一致
+#[no_mangle]
+pub unsafe extern "C" fn deallocate(ptr: *mut c_void, capacity: usize) {
+    let _ = Vec::from_raw_parts(ptr as *mut u8, 0, capacity);
+}
+
+/// 释放导出方法返回的字符串
+///
+/// # Safety
+/// ptr 须为导出方法返回的字符串指针，且只释放一次
+#[no_mangle]
+pub unsafe extern "C" fn drop_string(ptr: *mut c_char) {
+    if !ptr.is_null() {
+        let _ = CString::from_raw(ptr);
+    }
+}
+
+// 数据长度转换为导入函数参数，超出 i32 范围时返回错误
+fn abi_len(len: usize) -> Result<i32, ContractError> {
+    i32::try_from(len).map_err(|_| ContractError::Encode(format!("length {} exceeds i32", len)))
+}
+
+/// 读取运行时传入的 C 字符串，空指针或非 UTF-8 时返回错误
+///
+/// 运行时以 NUL 结尾的 C 字符串传入导出方法的字符串参数，并保证其在调用期间有效，
+/// 因此按 NUL 确定长度；字符串内容本身不含 NUL
+///
+/// # Safety
+/// 非空指针须指向以 NUL 结尾且在返回值生命周期内有效的内存
+pub unsafe fn str_from_ptr<'a>(ptr: *const c_char) -> Result<&'a str, ContractError> {
+    if ptr.is_null() {
+        return Err(ContractError::NullPointer);
+    }
+    Ok(CStr::from_ptr(ptr).to_str()?)
+}
+
+// 转换为交由运行时读取的 C 字符串，包含 NUL 字符时返回错误
+pub fn string_into_ptr(value: String) -> Result<*mut c_char, ContractError> {
+    CString::new(value)
+        .map(CString::into_raw)
+        .map_err(|e| ContractError::Encode(e.to_string()))
+}
+
+// 基于 sys_call/sys_msg 导入函数的宿主交互实现
+// 默认绑定运行时导入函数，本地测试可通过 with_imports 替换为模拟实现
+#[derive(Clone, Copy)]
+pub struct WasmHost {
+    sys_call: SysCallFn,
+    sys_msg: SysMsgFn,
+}
+
+impl Default for WasmHost {
+    fn default() -> Self {
+        WasmHost::with_imports(sys_call, sys_msg)
+    }
+}
+
+impl WasmHost {
+    pub fn with_imports(sys_call: SysCallFn, sys_msg: SysMsgFn) -> Self {
+        WasmHost { sys_call, sys_msg }
+    }
+
+    // 请求数据以借用指针传入，调用返回后仍归合约所有，无需宿主释放
+    fn sys_call(&self, req: &[u8]) -> Result<usize, ContractError> {
+        let req_len = abi_len(req.len())?;
+        Ok(unsafe { (self.sys_call)(req_len, req.as_ptr() as *const c_char) })
+    }
+}
+
+impl HostBackend for WasmHost {
+    // 按宿主返回的长度分配缓冲区，由宿主写入后直接返回，不依赖 NUL 结尾
+    // sys_msg 须返回写入的缓冲区指针，返回其它指针时视为宿主未写入返回数据
+    fn call(&self, req: &[u8]) -> Result<Vec<u8>, ContractError> {
+        let msg_len = self.sys_call(req)?;
+        let mut msg = vec![0u8; msg_len];
+        let msg_ptr = msg.as_mut_ptr() as *mut c_char;
+        let written = unsafe { (self.sys_msg)(abi_len(msg_len)?, msg_ptr) };
+        if written != msg_ptr {
+            return Err(ContractError::Decode(format!("sys_msg returned buffer {:p}, expect {:p}", written, msg_ptr)));
+        }
+
+        Ok(msg)
+    }
+
+    fn send(&self, req: &[u8]) {
+        let _ = self.sys_call(req);
+    }
+}
+
+// 从导出方法参数解码
+pub trait FromAbi: Sized {
+    // 导出方法中的参数类型
+    type Abi;
+
+    /// # Safety
+    /// 指针类参数须由运行时传入，且在调用期间有效
+    unsafe fn from_abi(abi: Self::Abi) -> Result<Self, ContractError>;
+}
+
+// 编码为导出方法返回值
+pub trait IntoAbi {
+    // 导出方法中的返回类型
+    type Abi;
+
+    fn into_abi(self) -> Result<Self::Abi, ContractError>;
+}
+
+// 导出方法执行失败，通过宿主日志上报错误后终止合约执行，由运行时回滚交易
+// 本地构建时宿主导入函数不可用，直接 panic
+pub fn abort(err: ContractError) -> ! {
+    let msg = format!("contract method failed: {}", err);
+    #[cfg(target_arch = "wasm32")]
+    {
+        crate::jdcc_api::LedgerService::default().logger().error(msg);
+        std::process::abort()
+    }
+    #[cfg(not(target_arch = "wasm32"))]
+    panic!("{}", msg)
+}
+
+macro_rules! impl_primitive_abi {
+    ($($ty:ty),*) => {
+        $(
+            impl FromAbi for $ty {
+                type Abi = $ty;
+
+                unsafe fn from_abi(abi: $ty) -> Result<Self, ContractError> {
+                    Ok(abi)
+                }
+            }
+
+            impl IntoAbi for $ty {
+                type Abi = $ty;
+
+                fn into_abi(self) -> Result<$ty, ContractError> {
+                    Ok(self)
+                }
+            }
+        )*
+    };
+}
+
+impl_primitive_abi!(bool, i32, i64, u32, u64);
+
+impl IntoAbi for () {
+    type Abi = ();
+
+    fn into_abi(self) -> Result<(), ContractError> {
+        Ok(())
+    }
+}
+
+// 字符串参数由运行时以 C 字符串传入
+impl FromAbi for String {
+    type Abi = *mut c_char;
+
+    unsafe fn from_abi(abi: *mut c_char) -> Result<Self, ContractError> {
+        Ok(str_from_ptr(abi)?.to_string())
+    }
+}
+
+// 字符串返回值交由运行时读取，通过 drop_string 释放
+impl IntoAbi for String {
+    type Abi = *mut c_char;
+
+    fn into_abi(self) -> Result<*mut c_char, ContractError> {
+        string_into_ptr(self)
+    }
+}
+
+// 地址、哈希、公钥参数以 Base58 C 字符串传入，返回值同样以 Base58 C 字符串返回
+macro_rules! impl_base58_abi {
+    ($($ty:ty),*) => {
+        $(
+            impl FromAbi for $ty {
+                type Abi = *mut c_char;
+
+                unsafe fn from_abi(abi: *mut c_char) -> Result<Self, ContractError> {
+                    str_from_ptr(abi)?.parse()
+                }
+            }
+
+            impl IntoAbi for $ty {
+                type Abi = *mut c_char;
+
+                fn into_abi(self) -> Result<*mut c_char, ContractError> {
+                    string_into_ptr(self.to_string())
+                }
+            }
+        )*
+    };
+}
+
+impl_base58_abi!(Address, HashDigest, PubKey);
+
+// 签名算法参数以算法名称 C 字符串传入
+impl FromAbi for Algorithm {
+    type Abi = *mut c_char;
+
+    unsafe fn from_abi(abi: *mut c_char) -> Result<Self, ContractError> {
+        str_from_ptr(abi)?.parse()
+    }
+}
+
+// 方法返回错误时终止合约执行
+impl<T: IntoAbi> IntoAbi for Result<T, ContractError> {
+    type Abi = T::Abi;
+
+    fn into_abi(self) -> Result<T::Abi, ContractError> {
+        self?.into_abi()
+    }
+}
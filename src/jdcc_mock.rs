@@ -0,0 +1,653 @@
+// JD Chain Mock Ledger
+// 内存账本，实现合约运行时数据交互协议，用于本地单元测试
+
+use std::cell::RefCell;
+use std::collections::{BTreeSet, HashMap};
+use std::mem;
+use std::rc::Rc;
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use crate::jdcc_api::HostBackend;
+use crate::jdcc_types::*;
+
+// 数据账户
+struct Account {
+    // 数据账户公钥
+    pubkey: PubKey,
+    // key -> 各版本数据，按版本顺序保存
+    entries: HashMap<String, Vec<DataValue>>,
+    // key 首次写入顺序
+    keys: Vec<String>,
+}
+
+impl Account {
+    fn new(pubkey: PubKey) -> Self {
+        Account { pubkey, entries: HashMap::new(), keys: Vec::new() }
+    }
+
+    // 最新版本，数据不存在时为 -1
+    fn version(&self, key: &str) -> i64 {
+        self.entries.get(key).map_or(-1, |versions| versions.len() as i64 - 1)
+    }
+}
+
+// 角色权限
+#[derive(Default)]
+struct Role {
+    ledger: BTreeSet<LedgerPermission>,
+    transaction: BTreeSet<TransactionPermission>,
+}
+
+impl Role {
+    fn configure(&mut self, config: &RoleConfig) {
+        self.ledger.extend(config.enable_ledger.iter().copied());
+        self.transaction.extend(config.enable_transaction.iter().copied());
+        for permission in &config.disable_ledger {
+            self.ledger.remove(permission);
+        }
+        for permission in &config.disable_transaction {
+            self.transaction.remove(permission);
+        }
+    }
+}
+
+// 用户授权，授予的角色及多角色权限合并策略
+type Authorization = (BTreeSet<String>, RolesPolicy);
+
+// 事件账户，topic -> 各序号事件内容及交易哈希
+type EventAccount = HashMap<String, Vec<(DataValue, HashDigest)>>;
+
+// 账本状态
+struct State {
+    ledger_hash: HashDigest,
+    block_height: i64,
+    block_hash: HashDigest,
+    participants: Vec<Participant>,
+    settings: LedgerSettings,
+    contract_address: Address,
+    tx_hash: HashDigest,
+    tx_time: u64,
+    signers: Vec<Address>,
+    users: HashMap<Address, PubKey>,
+    accounts: HashMap<Address, Account>,
+    event_accounts: HashMap<Address, EventAccount>,
+    roles: HashMap<String, Role>,
+    authorizations: HashMap<Address, Authorization>,
+    logs: Vec<String>,
+}
+
+// 合约实现，参数为所在账本、方法名及调用参数，返回方法返回值
+type Contract = Rc<dyn Fn(&MockLedger, &str, Vec<DataValue>) -> Result<Option<DataValue>, ContractError>>;
+
+// 内存账本
+pub struct MockLedger {
+    state: RefCell<State>,
+    // 合约地址 -> 合约实现
+    contracts: RefCell<HashMap<Address, Contract>>,
+}
+
+impl Default for MockLedger {
+    fn default() -> Self {
+        MockLedger::new()
+    }
+}
+
+// 请求处理失败
+struct Failure {
+    code: ResultCode,
+    sub_code: Option<ErrorCode>,
+    message: String,
+}
+
+impl Failure {
+    fn new(code: ResultCode, sub_code: Option<ErrorCode>, message: String) -> Self {
+        Failure { code, sub_code, message }
+    }
+
+    fn account_not_found(address: &str) -> Self {
+        Failure::new(ResultCode::NotFound, Some(ErrorCode::AccountNotFound), format!("account {} not found", address))
+    }
+
+    fn role_not_found(role: &str) -> Self {
+        Failure::new(ResultCode::NotFound, Some(ErrorCode::KeyNotFound), format!("role {} not found", role))
+    }
+}
+
+type Handled = Result<Message, Failure>;
+
+// 按 seed 及签名算法生成确定性的公钥
+fn derive(tag: &str, seed: &str, algorithm: Algorithm) -> PubKey {
+    let hash = HashDigest::sha256(format!("{}:{}:{}", tag, algorithm, seed).as_bytes());
+    PubKey::new(algorithm.code(), hash.digest())
+}
+
+// 默认共识节点
+fn node(id: i32) -> Participant {
+    let pubkey = derive("node", &id.to_string(), Algorithm::Ed25519);
+    Participant { id, name: format!("node{}", id), address: pubkey.address(), pubkey, state: ParticipantState::Consensus }
+}
+
+fn address(text: &str) -> Result<Address, Failure> {
+    Address::parse(text).map_err(|e| Failure::new(ResultCode::IllegalArgument, None, e.to_string()))
+}
+
+fn pubkey(text: &str) -> Result<PubKey, Failure> {
+    PubKey::parse(text).map_err(|e| Failure::new(ResultCode::IllegalArgument, None, e.to_string()))
+}
+
+fn parse<T: DeserializeOwned>(req: &str) -> Result<T, Failure> {
+    serde_json::from_str(req).map_err(|e| Failure::new(ResultCode::IllegalArgument, None, e.to_string()))
+}
+
+fn reply<T: Serialize>(result: &T) -> Handled {
+    Ok(Message::json(serde_json::to_string(result).unwrap()))
+}
+
+impl MockLedger {
+    pub fn new() -> Self {
+        let state = State {
+            ledger_hash: HashDigest::sha256(b"mock ledger"),
+            block_height: 0,
+            block_hash: HashDigest::sha256(b"mock block"),
+            participants: (0..4).map(node).collect(),
+            settings: LedgerSettings {
+                consensus_provider: "com.jd.blockchain.consensus.bftsmart.BftsmartConsensusProvider".to_string(),
+                hash_algorithm: CryptoAlgorithm::new(ALGORITHM_SHA256, "SHA256"),
+                crypto_algorithms: vec![
+                    CryptoAlgorithm::new(ALGORITHM_ED25519, "ED25519"),
+                    CryptoAlgorithm::new(ALGORITHM_ECDSA, "ECDSA"),
+                    CryptoAlgorithm::new(ALGORITHM_RSA, "RSA"),
+                    CryptoAlgorithm::new(ALGORITHM_SHA256, "SHA256"),
+                    CryptoAlgorithm::new(ALGORITHM_RIPEMD160, "RIPEMD160"),
+                    CryptoAlgorithm::new(ALGORITHM_SM2, "SM2"),
+                    CryptoAlgorithm::new(ALGORITHM_SM3, "SM3"),
+                ],
+                auto_verify_hash: true,
+            },
+            contract_address: derive("contract", "mock", Algorithm::Ed25519).address(),
+            tx_hash: HashDigest::sha256(b"mock tx"),
+            tx_time: 0,
+            signers: Vec::new(),
+            users: HashMap::new(),
+            accounts: HashMap::new(),
+            event_accounts: HashMap::new(),
+            roles: HashMap::new(),
+            authorizations: HashMap::new(),
+            logs: Vec::new(),
+        };
+        MockLedger { state: RefCell::new(state), contracts: RefCell::new(HashMap::new()) }
+    }
+
+    // 设置账本哈希
+    pub fn with_ledger_hash(self, hash: &HashDigest) -> Self {
+        self.state.borrow_mut().ledger_hash = hash.clone();
+        self
+    }
+
+    // 设置最新区块高度及哈希
+    pub fn with_latest_block(self, height: i64, hash: &HashDigest) -> Self {
+        {
+            let mut state = self.state.borrow_mut();
+            state.block_height = height;
+            state.block_hash = hash.clone();
+        }
+        self
+    }
+
+    // 设置参与方列表，默认为 4 个共识节点
+    pub fn with_participants(self, participants: &[Participant]) -> Self {
+        self.state.borrow_mut().participants = participants.to_vec();
+        self
+    }
+
+    // 设置账本配置
+    pub fn with_ledger_settings(self, settings: &LedgerSettings) -> Self {
+        self.state.borrow_mut().settings = settings.clone();
+        self
+    }
+
+    // 设置合约地址
+    pub fn with_contract_address(self, address: &Address) -> Self {
+        self.state.borrow_mut().contract_address = address.clone();
+        self
+    }
+
+    // 设置交易哈希
+    pub fn with_tx_hash(self, hash: &HashDigest) -> Self {
+        self.state.borrow_mut().tx_hash = hash.clone();
+        self
+    }
+
+    // 设置交易时间
+    pub fn with_tx_time(self, time: u64) -> Self {
+        self.state.borrow_mut().tx_time = time;
+        self
+    }
+
+    // 设置交易签名用户地址列表
+    pub fn with_signers(self, signers: &[Address]) -> Self {
+        self.state.borrow_mut().signers = signers.to_vec();
+        self
+    }
+
+    // 预置用户
+    pub fn with_user(self, address: &Address, pubkey: &PubKey) -> Self {
+        self.state.borrow_mut().users.insert(address.clone(), pubkey.clone());
+        self
+    }
+
+    // 预置数据账户
+    pub fn with_data_account(self, address: &Address, pubkey: &PubKey) -> Self {
+        self.state.borrow_mut().accounts.insert(address.clone(), Account::new(pubkey.clone()));
+        self
+    }
+
+    // 预置事件账户
+    pub fn with_event_account(self, address: &Address) -> Self {
+        self.state.borrow_mut().event_accounts.insert(address.clone(), EventAccount::new());
+        self
+    }
+
+    // 预置角色
+    pub fn with_role(self, config: &RoleConfig) -> Self {
+        self.state.borrow_mut().roles.entry(config.role.clone()).or_default().configure(config);
+        self
+    }
+
+    // 预置用户角色
+    pub fn with_user_roles(self, address: &Address, roles: &[&str], policy: RolesPolicy) -> Self {
+        let roles = roles.iter().map(|role| role.to_string()).collect();
+        self.state.borrow_mut().authorizations.insert(address.clone(), (roles, policy));
+        self
+    }
+
+    // 部署合约，被调用时合约地址切换为 address
+    // 合约实现可基于同一账本构建 LedgerService，以测试多合约调用
+    pub fn with_contract<F>(self, address: &Address, contract: F) -> Self
+    where
+        F: Fn(&MockLedger, &str, Vec<DataValue>) -> Result<Option<DataValue>, ContractError> + 'static,
+    {
+        self.contracts.borrow_mut().insert(address.clone(), Rc::new(contract));
+        self
+    }
+
+    // 合约输出的日志
+    pub fn logs(&self) -> Vec<String> {
+        self.state.borrow().logs.clone()
+    }
+
+    // 取出并清空合约输出的日志
+    pub fn take_logs(&self) -> Vec<String> {
+        mem::take(&mut self.state.borrow_mut().logs)
+    }
+
+    // 处理一次请求，请求与返回均为 Message 编码后的数据
+    pub fn handle(&self, req: &[u8]) -> Vec<u8> {
+        let ret = Message::decode(req.to_vec())
+            .map_err(|e| Failure::new(ResultCode::IllegalArgument, None, e.to_string()))
+            .and_then(|req| self.dispatch(&req.header, req.payload));
+        match ret {
+            Ok(msg) => msg.encode(),
+            Err(failure) => serde_json::to_vec(&Response {
+                rc: failure.code,
+                ec: failure.sub_code,
+                em: Some(failure.message),
+            }).unwrap(),
+        }
+    }
+
+    fn dispatch(&self, req: &str, payload: Option<Vec<u8>>) -> Handled {
+        let request: Request = parse(req)?;
+        if request.rt == INVOKE_CONTRACT {
+            return self.invoke(req, payload);
+        }
+        let mut state = self.state.borrow_mut();
+        match request.rt {
+            LOG => {
+                let log: LogRequest = parse(req)?;
+                let level = match log.l {
+                    LEVEL_DEBUG => "DEBUG",
+                    LEVEL_INFO => "INFO",
+                    LEVEL_ERROR => "ERROR",
+                    _ => "UNKNOWN",
+                };
+                state.logs.push(format!("[{}] {}", level, log.m));
+                reply(&Response { rc: ResultCode::Success, ec: None, em: None })
+            }
+            BEFORE_EVENT | POST_EVENT => reply(&Response { rc: ResultCode::Success, ec: None, em: None }),
+            GET_LEDGER_HASH => reply(&GetLedgerHashResult { rc: ResultCode::Success, lh: Some(state.ledger_hash.clone()) }),
+            GET_CONTRACT_ADDRESS => reply(&GetContractAddressResult { rc: ResultCode::Success, ca: Some(state.contract_address.clone()) }),
+            GET_TX_HASH => reply(&GetTxHashResult { rc: ResultCode::Success, th: Some(state.tx_hash.clone()) }),
+            GET_TX_TIME => reply(&GetTxTimeResult { rc: ResultCode::Success, tt: Some(state.tx_time) }),
+            GET_SIGNERS => reply(&GetSignersResult { rc: ResultCode::Success, ss: Some(state.signers.clone()) }),
+            GET_LATEST_BLOCK_HEIGHT => reply(&GetLatestBlockHeightResult { rc: ResultCode::Success, h: Some(state.block_height) }),
+            GET_LATEST_BLOCK_HASH => reply(&GetLatestBlockHashResult { rc: ResultCode::Success, bh: Some(state.block_hash.clone()) }),
+            GET_PARTICIPANTS => reply(&GetParticipantsResult { rc: ResultCode::Success, ps: Some(state.participants.clone()) }),
+            GET_LEDGER_SETTINGS => reply(&GetLedgerSettingsResult {
+                rc: ResultCode::Success,
+                cp: Some(state.settings.consensus_provider.clone()),
+                ha: Some(state.settings.hash_algorithm.clone()),
+                cas: Some(state.settings.crypto_algorithms.clone()),
+                vh: Some(state.settings.auto_verify_hash),
+            }),
+            REGISTER_USER => {
+                let register: RegisterUserRequest = parse(req)?;
+                register_user(&mut state, derive("user", &register.s, register.a))
+            }
+            REGISTER_USER_WITH_PUBKEY => {
+                let register: RegisterUserWithPubkeyRequest = parse(req)?;
+                register_user(&mut state, pubkey(&register.pk)?)
+            }
+            GET_USER => {
+                let get: GetUserRequest = parse(req)?;
+                let address = address(&get.a)?;
+                match state.users.get(&address) {
+                    Some(pubkey) => reply(&GetUserResult { rc: ResultCode::Success, a: Some(address.clone()), pk: Some(pubkey.clone()) }),
+                    None => Err(Failure::account_not_found(&get.a)),
+                }
+            }
+            REGISTER_DATA_ACCOUNT => {
+                let register: RegisterDataAccountRequest = parse(req)?;
+                register_data_account(&mut state, derive("data", &register.s, register.a))
+            }
+            REGISTER_DATA_ACCOUNT_WITH_PUBKEY => {
+                let register: RegisterDataAccountWithPubkeyRequest = parse(req)?;
+                register_data_account(&mut state, pubkey(&register.pk)?)
+            }
+            GET_DATA_ACCOUNT => {
+                let get: GetDataAccountRequest = parse(req)?;
+                let address = address(&get.a)?;
+                match state.accounts.get(&address) {
+                    Some(account) => reply(&GetDataAccountResult { rc: ResultCode::Success, a: Some(address.clone()), pk: Some(account.pubkey.clone()) }),
+                    None => Err(Failure::account_not_found(&get.a)),
+                }
+            }
+            SET_TEXT => {
+                let set: SetTextRequest = parse(req)?;
+                set_value(&mut state, &set.a, &set.k, DataValue::Text(set.v), None)
+            }
+            SET_TEXT_WITH_VERSION => {
+                let set: SetTextWithVersionRequest = parse(req)?;
+                set_value(&mut state, &set.a, &set.k, DataValue::Text(set.v), Some(set.ver))
+            }
+            SET_INT64 => {
+                let set: SetInt64Request = parse(req)?;
+                set_value(&mut state, &set.a, &set.k, DataValue::Int64(set.v), None)
+            }
+            SET_INT64_WITH_VERSION => {
+                let set: SetInt64WithVersionRequest = parse(req)?;
+                set_value(&mut state, &set.a, &set.k, DataValue::Int64(set.v), Some(set.ver))
+            }
+            SET_BYTES => {
+                let set: SetBytesRequest = parse(req)?;
+                set_value(&mut state, &set.a, &set.k, DataValue::Bytes(required_payload(payload)?), None)
+            }
+            SET_BYTES_WITH_VERSION => {
+                let set: SetBytesWithVersionRequest = parse(req)?;
+                set_value(&mut state, &set.a, &set.k, DataValue::Bytes(required_payload(payload)?), Some(set.ver))
+            }
+            SET_VALUE => {
+                let set: SetValueRequest = parse(req)?;
+                set_value(&mut state, &set.a, &set.k, wire_value(set.t, set.v, payload)?, None)
+            }
+            SET_VALUE_WITH_VERSION => {
+                let set: SetValueWithVersionRequest = parse(req)?;
+                set_value(&mut state, &set.a, &set.k, wire_value(set.t, set.v, payload)?, Some(set.ver))
+            }
+            GET_VALUE_VERSION => {
+                let get: GetValueVersionRequest = parse(req)?;
+                let account = state.accounts.get(&address(&get.a)?).ok_or_else(|| Failure::account_not_found(&get.a))?;
+                reply(&GetValueVersionResult { rc: ResultCode::Success, ver: Some(account.version(&get.k)) })
+            }
+            GET_VALUE => {
+                let get: GetValueRequest = parse(req)?;
+                let account = state.accounts.get(&address(&get.a)?).ok_or_else(|| Failure::account_not_found(&get.a))?;
+                let latest = account.version(&get.k);
+                let version = if get.ver < 0 { latest } else { get.ver };
+                if latest < 0 || version > latest {
+                    return Err(Failure::new(ResultCode::NotFound, Some(ErrorCode::KeyNotFound), format!("key {} at version {} not found", get.k, get.ver)));
+                }
+                let (value_type, v, payload) = account.entries[&get.k][version as usize].clone().into_wire();
+                let result = GetValueResult { rc: ResultCode::Success, k: Some(get.k.clone()), v, t: Some(value_type), ver: Some(version) };
+                let header = serde_json::to_string(&result).unwrap();
+                Ok(Message { header, payload })
+            }
+            GET_DATA_ENTRIES => {
+                let get: GetDataEntriesRequest = parse(req)?;
+                let account = state.accounts.get(&address(&get.a)?).ok_or_else(|| Failure::account_not_found(&get.a))?;
+                let mut payload: Option<Vec<u8>> = None;
+                let entries = account.keys.iter().skip(get.f as usize).take(get.c as usize).map(|key| {
+                    let versions = &account.entries[key];
+                    let (t, v, bytes) = versions[versions.len() - 1].clone().into_wire();
+                    let l = bytes.map(|bytes| {
+                        payload.get_or_insert_with(Vec::new).extend_from_slice(&bytes);
+                        bytes.len()
+                    });
+                    DataEntryResult { k: key.clone(), ver: versions.len() as i64 - 1, t, v, l }
+                }).collect();
+                let result = GetDataEntriesResult { rc: ResultCode::Success, es: Some(entries) };
+                Ok(Message { header: serde_json::to_string(&result).unwrap(), payload })
+            }
+            GET_DATA_ENTRIES_TOTAL_COUNT => {
+                let get: GetDataEntriesTotalCountRequest = parse(req)?;
+                let account = state.accounts.get(&address(&get.a)?).ok_or_else(|| Failure::account_not_found(&get.a))?;
+                reply(&GetDataEntriesTotalCountResult { rc: ResultCode::Success, tc: Some(account.keys.len() as u64) })
+            }
+            REGISTER_EVENT_ACCOUNT => {
+                let register: RegisterEventAccountRequest = parse(req)?;
+                let pubkey = derive("event", &register.s, register.a);
+                let address = pubkey.address();
+                if state.event_accounts.contains_key(&address) {
+                    return Err(Failure::new(ResultCode::Error, Some(ErrorCode::AccountExists), format!("event account {} already exists", address)));
+                }
+                state.event_accounts.insert(address.clone(), EventAccount::new());
+                reply(&RegisterEventAccountResult { rc: ResultCode::Success, a: Some(address) })
+            }
+            PUBLISH_EVENT => {
+                let publish: PublishEventRequest = parse(req)?;
+                let tx_hash = state.tx_hash.clone();
+                let account = state.event_accounts.get_mut(&address(&publish.a)?).ok_or_else(|| Failure::account_not_found(&publish.a))?;
+                let content = wire_value(publish.t, publish.v, payload)?;
+                let events = account.entry(publish.tp.clone()).or_default();
+                let latest = events.len() as i64 - 1;
+                if publish.seq != latest {
+                    return Err(Failure::new(ResultCode::Error, Some(ErrorCode::VersionConflict),
+                                            format!("sequence conflict on topic {}, expected {} but latest is {}", publish.tp, publish.seq, latest)));
+                }
+                events.push((content, tx_hash));
+                reply(&PublishEventResult { rc: ResultCode::Success, seq: Some(latest + 1) })
+            }
+            GET_LATEST_EVENT => {
+                let get: GetLatestEventRequest = parse(req)?;
+                let account = state.event_accounts.get(&address(&get.a)?).ok_or_else(|| Failure::account_not_found(&get.a))?;
+                let events = account.get(&get.tp).filter(|events| !events.is_empty())
+                    .ok_or_else(|| Failure::new(ResultCode::NotFound, Some(ErrorCode::KeyNotFound), format!("no event on topic {}", get.tp)))?;
+                let (content, tx_hash) = events[events.len() - 1].clone();
+                let (content_type, v, payload) = content.into_wire();
+                let result = GetLatestEventResult {
+                    rc: ResultCode::Success,
+                    tp: Some(get.tp.clone()),
+                    v,
+                    t: Some(content_type),
+                    seq: Some(events.len() as i64 - 1),
+                    th: Some(tx_hash),
+                };
+                Ok(Message { header: serde_json::to_string(&result).unwrap(), payload })
+            }
+            GET_ROLE_PRIVILEGE => {
+                let get: GetRolePrivilegeRequest = parse(req)?;
+                let role = state.roles.get(&get.r).ok_or_else(|| Failure::role_not_found(&get.r))?;
+                reply(&GetRolePrivilegeResult {
+                    rc: ResultCode::Success,
+                    r: Some(get.r.clone()),
+                    lp: Some(role.ledger.iter().copied().collect()),
+                    tp: Some(role.transaction.iter().copied().collect()),
+                })
+            }
+            GET_USER_ROLES => {
+                let get: GetUserRolesRequest = parse(req)?;
+                let address = user(&state, &get.a)?;
+                let (roles, policy) = state.authorizations.get(&address).cloned().unwrap_or_default();
+                reply(&GetUserRolesResult { rc: ResultCode::Success, a: Some(address), rs: Some(roles.into_iter().collect()), p: Some(policy) })
+            }
+            GET_USER_PRIVILEGE => {
+                let get: GetUserPrivilegeRequest = parse(req)?;
+                let address = user(&state, &get.a)?;
+                let (ledger, transaction) = privilege(&state, &address);
+                let roles = state.authorizations.get(&address).map(|(roles, _)| roles.iter().cloned().collect()).unwrap_or_default();
+                reply(&GetUserPrivilegeResult {
+                    rc: ResultCode::Success,
+                    a: Some(address),
+                    rs: Some(roles),
+                    lp: Some(ledger.into_iter().collect()),
+                    tp: Some(transaction.into_iter().collect()),
+                })
+            }
+            CONFIGURE_ROLE => {
+                let configure: ConfigureRoleRequest = parse(req)?;
+                authorize(&state, LedgerPermission::ConfigureRoles)?;
+                let config = RoleConfig {
+                    role: configure.r,
+                    enable_ledger: configure.el,
+                    disable_ledger: configure.dl,
+                    enable_transaction: configure.et,
+                    disable_transaction: configure.dt,
+                };
+                state.roles.entry(config.role.clone()).or_default().configure(&config);
+                reply(&Response { rc: ResultCode::Success, ec: None, em: None })
+            }
+            AUTHORIZE_USER_ROLES => {
+                let authorize_roles: AuthorizeUserRolesRequest = parse(req)?;
+                authorize(&state, LedgerPermission::AuthorizeUserRoles)?;
+                let address = user(&state, &authorize_roles.a)?;
+                if let Some(role) = authorize_roles.ar.iter().find(|role| !state.roles.contains_key(*role)) {
+                    return Err(Failure::role_not_found(role));
+                }
+                let (roles, policy) = state.authorizations.entry(address).or_default();
+                roles.extend(authorize_roles.ar);
+                for role in &authorize_roles.ur {
+                    roles.remove(role);
+                }
+                if let Some(p) = authorize_roles.p {
+                    *policy = p;
+                }
+                reply(&Response { rc: ResultCode::Success, ec: None, em: None })
+            }
+            rt => Err(Failure::new(ResultCode::Unsupported, None, format!("unsupported request type {}", rt))),
+        }
+    }
+
+    // 调用合约，调用期间不持有账本状态，被调合约可继续访问账本或调用其它合约
+    // 被调合约返回错误时不回滚其已写入的数据
+    fn invoke(&self, req: &str, payload: Option<Vec<u8>>) -> Handled {
+        let invoke: InvokeContractRequest = parse(req)?;
+        let address = address(&invoke.a)?;
+        let contract = self.contracts.borrow().get(&address).cloned().ok_or_else(|| Failure::account_not_found(&invoke.a))?;
+        let args = InvokeArg::decode(invoke.ps, payload).map_err(|e| Failure::new(ResultCode::IllegalArgument, Some(ErrorCode::InvalidValue), e.to_string()))?;
+
+        let caller = mem::replace(&mut self.state.borrow_mut().contract_address, address);
+        let ret = contract(self, &invoke.m, args);
+        self.state.borrow_mut().contract_address = caller;
+        match ret {
+            Ok(Some(value)) => {
+                let (value_type, v, payload) = value.into_wire();
+                let result = InvokeContractResult { rc: ResultCode::Success, v, t: Some(value_type) };
+                Ok(Message { header: serde_json::to_string(&result).unwrap(), payload })
+            }
+            Ok(None) => reply(&InvokeContractResult { rc: ResultCode::Success, v: None, t: None }),
+            Err(e) => Err(Failure::new(e.code().unwrap_or(ResultCode::Error), e.sub_code(), e.to_string())),
+        }
+    }
+}
+
+// 注册用户，地址由公钥生成
+fn register_user(state: &mut State, pubkey: PubKey) -> Handled {
+    let address = pubkey.address();
+    if state.users.contains_key(&address) {
+        return Err(Failure::new(ResultCode::Error, Some(ErrorCode::AccountExists), format!("user {} already exists", address)));
+    }
+    state.users.insert(address.clone(), pubkey);
+    reply(&RegisterUserResult { rc: ResultCode::Success, a: Some(address) })
+}
+
+// 注册数据账户，地址由公钥生成
+fn register_data_account(state: &mut State, pubkey: PubKey) -> Handled {
+    let address = pubkey.address();
+    if state.accounts.contains_key(&address) {
+        return Err(Failure::new(ResultCode::Error, Some(ErrorCode::AccountExists), format!("data account {} already exists", address)));
+    }
+    state.accounts.insert(address.clone(), Account::new(pubkey));
+    reply(&RegisterDataAccountResult { rc: ResultCode::Success, a: Some(address) })
+}
+
+// 已注册用户地址
+fn user(state: &State, address: &str) -> Result<Address, Failure> {
+    let address = self::address(address)?;
+    if !state.users.contains_key(&address) {
+        return Err(Failure::account_not_found(address.as_str()));
+    }
+    Ok(address)
+}
+
+// 按合并策略计算用户权限，忽略不存在的角色
+fn privilege(state: &State, address: &Address) -> (BTreeSet<LedgerPermission>, BTreeSet<TransactionPermission>) {
+    let (names, policy) = match state.authorizations.get(address) {
+        Some(authorization) => authorization,
+        None => return Default::default(),
+    };
+    let mut roles = names.iter().filter_map(|name| state.roles.get(name));
+    let first = match roles.next() {
+        Some(role) => (role.ledger.clone(), role.transaction.clone()),
+        None => return Default::default(),
+    };
+    roles.fold(first, |(ledger, transaction), role| match policy {
+        RolesPolicy::Union => (&ledger | &role.ledger, &transaction | &role.transaction),
+        RolesPolicy::Intersect => (&ledger & &role.ledger, &transaction & &role.transaction),
+    })
+}
+
+// 校验交易签名用户中至少一个具有账本权限
+fn authorize(state: &State, permission: LedgerPermission) -> Result<(), Failure> {
+    if state.signers.iter().any(|signer| privilege(state, signer).0.contains(&permission)) {
+        return Ok(());
+    }
+    Err(Failure::new(ResultCode::Unauthorized, Some(ErrorCode::PermissionDenied), format!("no signer has permission {:?}", permission)))
+}
+
+fn required_payload(payload: Option<Vec<u8>>) -> Result<Vec<u8>, Failure> {
+    payload.ok_or_else(|| Failure::new(ResultCode::IllegalArgument, None, "missing binary payload".to_string()))
+}
+
+// 解析写入的数据，二进制类型数据取自载荷
+fn wire_value(value_type: ValueType, value: Option<String>, payload: Option<Vec<u8>>) -> Result<DataValue, Failure> {
+    DataValue::from_wire(value_type, value, payload).map_err(|e| Failure::new(ResultCode::IllegalArgument, Some(ErrorCode::InvalidValue), e.to_string()))
+}
+
+// 写KV，指定版本时须与当前最新版本一致，否则版本冲突
+fn set_value(state: &mut State, address: &str, key: &str, value: DataValue, version: Option<i64>) -> Handled {
+    let account = state.accounts.get_mut(&self::address(address)?).ok_or_else(|| Failure::account_not_found(address))?;
+    let latest = account.version(key);
+    if let Some(version) = version {
+        if version != latest {
+            return Err(Failure::new(ResultCode::Error, Some(ErrorCode::VersionConflict),
+                                    format!("version conflict on key {}, expected {} but latest is {}", key, version, latest)));
+        }
+    }
+    if latest < 0 {
+        account.keys.push(key.to_string());
+    }
+    account.entries.entry(key.to_string()).or_default().push(value);
+    reply(&SetKVResult { rc: ResultCode::Success, ver: Some(latest + 1) })
+}
+
+impl HostBackend for MockLedger {
+    fn call(&self, req: &[u8]) -> Result<Vec<u8>, ContractError> {
+        Ok(self.handle(req))
+    }
+
+    fn send(&self, req: &[u8]) {
+        self.handle(req);
+    }
+}
@@ -0,0 +1,85 @@
+// JD Chain Contract Overlay
+// 单次合约调用内的 KV 读缓存：重复读取由内存返回
+// 写入直接提交宿主，成功后按宿主返回的版本更新缓存，版本与未启用缓存时一致
+// 另缓存交易签名用户地址列表
+
+use std::collections::HashMap;
+
+use crate::jdcc_types::*;
+
+// 缓存的 KV 数据
+struct Entry {
+    // 最新版本，数据不存在时为 -1
+    version: i64,
+    // 最新版本数据，仅查询过版本时为空
+    value: Option<DataValue>,
+}
+
+#[derive(Default)]
+pub(crate) struct Overlay {
+    entries: HashMap<(Address, String), Entry>,
+    // 交易签名用户地址列表
+    signers: Option<Vec<Address>>,
+}
+
+fn entry_key(address: &Address, key: &str) -> (Address, String) {
+    (address.clone(), key.to_string())
+}
+
+impl Overlay {
+    // 缓存的最新版本
+    pub(crate) fn version(&self, address: &Address, key: &str) -> Option<i64> {
+        self.entries.get(&entry_key(address, key)).map(|entry| entry.version)
+    }
+
+    // 读取缓存数据，外层 None 表示缓存未命中，内层 None 表示数据不存在
+    pub(crate) fn read(&self, address: &Address, key: &str, version: i64) -> Option<Option<KVData>> {
+        let entry = self.entries.get(&entry_key(address, key))?;
+        if version > entry.version || entry.version < 0 {
+            return Some(None);
+        }
+        if version >= 0 && version != entry.version {
+            return None;
+        }
+        let value = entry.value.clone()?;
+        Some(Some(KVData { key: key.to_string(), value, version: entry.version }))
+    }
+
+    // 记录宿主返回的版本
+    pub(crate) fn load_version(&mut self, address: &Address, key: &str, version: i64) {
+        self.entries.entry(entry_key(address, key)).or_insert(Entry { version, value: None });
+    }
+
+    // 记录宿主返回的最新数据
+    pub(crate) fn load(&mut self, address: &Address, key: &str, data: Option<&KVData>) {
+        let entry = match data {
+            Some(data) => Entry { version: data.version, value: Some(data.value.clone()) },
+            None => Entry { version: -1, value: None },
+        };
+        self.entries.insert(entry_key(address, key), entry);
+    }
+
+    // 记录已提交宿主的写入
+    pub(crate) fn store(&mut self, address: &Address, key: &str, value: DataValue, version: i64) {
+        self.entries.insert(entry_key(address, key), Entry { version, value: Some(value) });
+    }
+
+    // 移除缓存数据，后续读取重新查询宿主
+    pub(crate) fn invalidate(&mut self, address: &Address, key: &str) {
+        self.entries.remove(&entry_key(address, key));
+    }
+
+    pub(crate) fn signers(&self) -> Option<&[Address]> {
+        self.signers.as_deref()
+    }
+
+    pub(crate) fn load_signers(&mut self, signers: &[Address]) {
+        self.signers = Some(signers.to_vec());
+    }
+
+    // 丢弃全部缓存
+    pub(crate) fn clear(&mut self) {
+        self.entries.clear();
+        self.signers = None;
+    }
+}
@@ -0,0 +1,366 @@
+// JD Chain Contract Storage
+// 基于数据账户 KV 的类型化存储，值经 serde 编码后写入，写入时自动携带最新版本
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::marker::PhantomData;
+use std::slice;
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use crate::jdcc_api::{HostBackend, LedgerService};
+use crate::jdcc_ffi::WasmHost;
+use crate::jdcc_types::*;
+
+// 值编码方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Codec {
+    // JSON 文本，以 JSON 类型写入
+    Json,
+    // bincode 紧凑二进制，以 BYTES 类型写入
+    Binary,
+}
+
+impl Codec {
+    fn encode<T: Serialize + ?Sized>(&self, value: &T) -> Result<DataValue, ContractError> {
+        match self {
+            Codec::Json => DataValue::json(value),
+            Codec::Binary => bincode::serialize(value)
+                .map(DataValue::Bytes)
+                .map_err(|e| ContractError::Encode(e.to_string())),
+        }
+    }
+}
+
+// 按数据类型解码，与写入时的编码方式无关
+fn decode<T: DeserializeOwned>(value: &DataValue) -> Result<T, ContractError> {
+    match value {
+        DataValue::Json(v) | DataValue::Text(v) => Ok(serde_json::from_str(v)?),
+        DataValue::Bytes(v) => bincode::deserialize(v).map_err(|e| ContractError::Decode(e.to_string())),
+        other => Err(ContractError::Decode(format!("unsupported storage value type {:?}", other.value_type()))),
+    }
+}
+
+// 数据账户类型化存储
+// 记录已读写 key 的最新版本，写入时携带该版本，版本冲突时清除记录并返回错误
+pub struct Storage<'a, B = WasmHost> {
+    service: &'a LedgerService<B>,
+    address: Address,
+    codec: Codec,
+    versions: RefCell<HashMap<String, i64>>,
+}
+
+impl<'a, B: HostBackend> Storage<'a, B> {
+    // 默认使用 JSON 编码
+    pub fn new(service: &'a LedgerService<B>, address: &Address) -> Self {
+        Storage { service, address: address.clone(), codec: Codec::Json, versions: RefCell::new(HashMap::new()) }
+    }
+
+    pub fn with_codec(mut self, codec: Codec) -> Self {
+        self.codec = codec;
+        self
+    }
+
+    pub fn service(&self) -> &'a LedgerService<B> {
+        self.service
+    }
+
+    pub fn address(&self) -> &Address {
+        &self.address
+    }
+
+    pub fn codec(&self) -> Codec {
+        self.codec
+    }
+
+    // 查询最新版本，数据不存在时返回 -1
+    pub fn version(&self, key: &str) -> Result<i64, ContractError> {
+        if let Some(version) = self.versions.borrow().get(key) {
+            return Ok(*version);
+        }
+        let version = self.service.get_value_version(&self.address, key)?;
+        self.versions.borrow_mut().insert(key.to_string(), version);
+        Ok(version)
+    }
+
+    pub fn contains(&self, key: &str) -> Result<bool, ContractError> {
+        Ok(self.version(key)? >= 0)
+    }
+
+    // 读取最新版本数据，数据不存在时返回 None
+    pub fn get<T: DeserializeOwned>(&self, key: &str) -> Result<Option<T>, ContractError> {
+        match self.service.get_value(&self.address, key, -1)? {
+            Some(data) => {
+                self.versions.borrow_mut().insert(key.to_string(), data.version);
+                Ok(Some(decode(&data.value)?))
+            }
+            None => {
+                self.versions.borrow_mut().insert(key.to_string(), -1);
+                Ok(None)
+            }
+        }
+    }
+
+    // 读取数据，数据不存在时返回默认值
+    pub fn get_or_default<T: DeserializeOwned + Default>(&self, key: &str) -> Result<T, ContractError> {
+        Ok(self.get(key)?.unwrap_or_default())
+    }
+
+    // 写入数据，返回写入后的版本
+    pub fn set<T: Serialize + ?Sized>(&self, key: &str, value: &T) -> Result<i64, ContractError> {
+        let value = self.codec.encode(value)?;
+        let version = self.version(key)?;
+        match self.service.set_value_with_version(&self.address, key, value, version) {
+            Ok(version) => {
+                self.versions.borrow_mut().insert(key.to_string(), version);
+                Ok(version)
+            }
+            Err(e) => {
+                self.versions.borrow_mut().remove(key);
+                Err(e)
+            }
+        }
+    }
+}
+
+impl<B: HostBackend> Storage<'_, B> {
+    // 以 prefix 为前缀的持久化映射
+    pub fn map<K: StorageKey + ?Sized, V: Serialize + DeserializeOwned>(&self, prefix: &str) -> StorageMap<'_, K, V, B> {
+        StorageMap { storage: self, prefix: prefix.to_string(), _marker: PhantomData }
+    }
+
+    // 以 prefix 为前缀的持久化列表
+    pub fn vec<T: Serialize + DeserializeOwned>(&self, prefix: &str) -> StorageVec<'_, T, B> {
+        StorageVec { storage: self, prefix: prefix.to_string(), _marker: PhantomData }
+    }
+
+    // 以 prefix 为前缀的持久化集合
+    pub fn set_of<T: StorageKey + ?Sized>(&self, prefix: &str) -> StorageSet<'_, T, B> {
+        StorageSet { map: self.map(prefix) }
+    }
+}
+
+// 集合元素 key 编码，编码结果须唯一
+pub trait StorageKey {
+    fn storage_key(&self) -> String;
+}
+
+impl StorageKey for str {
+    fn storage_key(&self) -> String {
+        self.to_string()
+    }
+}
+
+impl StorageKey for String {
+    fn storage_key(&self) -> String {
+        self.clone()
+    }
+}
+
+impl<K: StorageKey + ?Sized> StorageKey for &K {
+    fn storage_key(&self) -> String {
+        (**self).storage_key()
+    }
+}
+
+impl StorageKey for Address {
+    fn storage_key(&self) -> String {
+        self.as_str().to_string()
+    }
+}
+
+macro_rules! impl_display_key {
+    ($($ty:ty),*) => {
+        $(
+            impl StorageKey for $ty {
+                fn storage_key(&self) -> String {
+                    self.to_string()
+                }
+            }
+        )*
+    };
+}
+
+impl_display_key!(bool, char, i8, i16, i32, i64, i128, u8, u16, u32, u64, u128);
+
+// 集合元素 key 为 "{prefix}/{key}"，长度计数 key 为 "{prefix}.len"
+// 元素 key 中的 '%'、'/'、'.' 转义为 %25、%2F、%2E，使不同前缀集合的 key 互不重叠
+fn entry_key(prefix: &str, key: &str) -> String {
+    let mut entry = String::with_capacity(prefix.len() + key.len() + 1);
+    entry.push_str(prefix);
+    entry.push('/');
+    for c in key.chars() {
+        match c {
+            '%' => entry.push_str("%25"),
+            '/' => entry.push_str("%2F"),
+            '.' => entry.push_str("%2E"),
+            c => entry.push(c),
+        }
+    }
+    entry
+}
+
+fn len_key(prefix: &str) -> String {
+    format!("{}.len", prefix)
+}
+
+// 持久化映射，记录元素个数，不支持遍历
+// 元素值按存储编码写为单元素列表 [value]，以区分值本身为空（如 Option::None）与元素不存在
+// KV 数据无法删除，移除元素时写入空列表 [] 作为删除标记，读取到 [] 或 key 不存在均视为元素不存在
+pub struct StorageMap<'a, K: ?Sized, V, B = WasmHost> {
+    storage: &'a Storage<'a, B>,
+    prefix: String,
+    _marker: PhantomData<fn(&K) -> V>,
+}
+
+impl<K: StorageKey + ?Sized, V: Serialize + DeserializeOwned, B: HostBackend> StorageMap<'_, K, V, B> {
+    pub fn prefix(&self) -> &str {
+        &self.prefix
+    }
+
+    pub fn len(&self) -> Result<u64, ContractError> {
+        self.storage.get_or_default(&len_key(&self.prefix))
+    }
+
+    pub fn is_empty(&self) -> Result<bool, ContractError> {
+        Ok(self.len()? == 0)
+    }
+
+    pub fn get(&self, key: &K) -> Result<Option<V>, ContractError> {
+        self.slot(&entry_key(&self.prefix, &key.storage_key()))
+    }
+
+    pub fn contains_key(&self, key: &K) -> Result<bool, ContractError> {
+        Ok(self.get(key)?.is_some())
+    }
+
+    // 写入元素，返回原值
+    pub fn insert(&self, key: &K, value: &V) -> Result<Option<V>, ContractError> {
+        let key = entry_key(&self.prefix, &key.storage_key());
+        let old = self.slot(&key)?;
+        self.storage.set(&key, slice::from_ref(value))?;
+        if old.is_none() {
+            self.storage.set(&len_key(&self.prefix), &(self.len()? + 1))?;
+        }
+        Ok(old)
+    }
+
+    // 移除元素，返回原值
+    pub fn remove(&self, key: &K) -> Result<Option<V>, ContractError> {
+        let key = entry_key(&self.prefix, &key.storage_key());
+        let old = self.slot(&key)?;
+        if old.is_some() {
+            self.storage.set::<[V]>(&key, &[])?;
+            // 元素可能绕过集合直接写入，计数不足时保持为 0
+            self.storage.set(&len_key(&self.prefix), &self.len()?.saturating_sub(1))?;
+        }
+        Ok(old)
+    }
+
+    fn slot(&self, key: &str) -> Result<Option<V>, ContractError> {
+        Ok(self.storage.get::<Vec<V>>(key)?.and_then(|mut slot| slot.pop()))
+    }
+}
+
+// 持久化列表，元素 key 为下标
+pub struct StorageVec<'a, T, B = WasmHost> {
+    storage: &'a Storage<'a, B>,
+    prefix: String,
+    _marker: PhantomData<fn(&T) -> T>,
+}
+
+impl<T: Serialize + DeserializeOwned, B: HostBackend> StorageVec<'_, T, B> {
+    pub fn prefix(&self) -> &str {
+        &self.prefix
+    }
+
+    pub fn len(&self) -> Result<u64, ContractError> {
+        self.storage.get_or_default(&len_key(&self.prefix))
+    }
+
+    pub fn is_empty(&self) -> Result<bool, ContractError> {
+        Ok(self.len()? == 0)
+    }
+
+    // 读取元素，下标越界或元素未写入时返回 None
+    pub fn get(&self, index: u64) -> Result<Option<T>, ContractError> {
+        if index >= self.len()? {
+            return Ok(None);
+        }
+        self.element(index)
+    }
+
+    // 替换元素，下标越界时返回错误
+    pub fn set(&self, index: u64, value: &T) -> Result<(), ContractError> {
+        let len = self.len()?;
+        if index >= len {
+            return Err(ContractError::IndexOutOfBounds { index, len });
+        }
+        self.storage.set(&entry_key(&self.prefix, &index.storage_key()), value)?;
+        Ok(())
+    }
+
+    // 追加元素，返回其下标
+    pub fn push(&self, value: &T) -> Result<u64, ContractError> {
+        let len = self.len()?;
+        self.storage.set(&entry_key(&self.prefix, &len.storage_key()), value)?;
+        self.storage.set(&len_key(&self.prefix), &(len + 1))?;
+        Ok(len)
+    }
+
+    // 移除最后一个元素，原数据保留在账本中，由后续 push 覆盖
+    // 最后一个元素未写入时仍缩减长度，返回 None
+    pub fn pop(&self) -> Result<Option<T>, ContractError> {
+        let len = self.len()?;
+        if len == 0 {
+            return Ok(None);
+        }
+        let value = self.element(len - 1)?;
+        self.storage.set(&len_key(&self.prefix), &(len - 1))?;
+        Ok(value)
+    }
+
+    // 按下标顺序逐个读取元素，跳过未写入的元素
+    pub fn iter(&self) -> Result<impl Iterator<Item = Result<T, ContractError>> + '_, ContractError> {
+        Ok((0..self.len()?).filter_map(move |index| self.element(index).transpose()))
+    }
+
+    // 长度计数可能绕过集合直接写入，下标范围内的元素不一定存在
+    fn element(&self, index: u64) -> Result<Option<T>, ContractError> {
+        self.storage.get(&entry_key(&self.prefix, &index.storage_key()))
+    }
+}
+
+// 持久化集合，记录元素个数，不支持遍历
+pub struct StorageSet<'a, T: ?Sized, B = WasmHost> {
+    map: StorageMap<'a, T, (), B>,
+}
+
+impl<T: StorageKey + ?Sized, B: HostBackend> StorageSet<'_, T, B> {
+    pub fn prefix(&self) -> &str {
+        self.map.prefix()
+    }
+
+    pub fn len(&self) -> Result<u64, ContractError> {
+        self.map.len()
+    }
+
+    pub fn is_empty(&self) -> Result<bool, ContractError> {
+        self.map.is_empty()
+    }
+
+    pub fn contains(&self, value: &T) -> Result<bool, ContractError> {
+        self.map.contains_key(value)
+    }
+
+    // 加入元素，元素已存在时返回 false
+    pub fn insert(&self, value: &T) -> Result<bool, ContractError> {
+        Ok(self.map.insert(value, &())?.is_none())
+    }
+
+    // 移除元素，元素不存在时返回 false
+    pub fn remove(&self, value: &T) -> Result<bool, ContractError> {
+        Ok(self.map.remove(value)?.is_some())
+    }
+}
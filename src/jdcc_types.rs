@@ -1,174 +1,1127 @@
 // Types for JD Chain Contract Data Interaction
 
 use std::ffi::CString;
+use std::fmt;
+use std::str::{FromStr, Utf8Error};
+use std::string::FromUtf8Error;
 
-use serde::{Deserialize, Serialize};
+use ripemd::Ripemd160;
+use serde::de::{self, DeserializeOwned};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use sha2::{Digest, Sha256};
 
 // request types
-const LOG: u8 = 0;
-const BEFORE_EVENT: u8 = 1;
-const POST_EVENT: u8 = 2;
-const GET_LEDGER_HASH: u8 = 3;
-const GET_CONTRACT_ADDRESS: u8 = 4;
-const GET_TX_HASH: u8 = 5;
-const GET_TX_TIME: u8 = 6;
-const GET_SIGNERS: u8 = 7;
-const REGISTER_USER: u8 = 8;
-const GET_USER: u8 = 9;
-const REGISTER_DATA_ACCOUNT: u8 = 10;
-const GET_DATA_ACCOUNT: u8 = 11;
-const SET_TEXT: u8 = 12;
-const SET_TEXT_WITH_VERSION: u8 = 13;
-const SET_INT64: u8 = 14;
-const SET_INT64_WITH_VERSION: u8 = 15;
-const GET_VALUE_VERSION: u8 = 16;
-const GET_VALUE: u8 = 17;
+pub(crate) const LOG: u8 = 0;
+pub(crate) const BEFORE_EVENT: u8 = 1;
+pub(crate) const POST_EVENT: u8 = 2;
+pub(crate) const GET_LEDGER_HASH: u8 = 3;
+pub(crate) const GET_CONTRACT_ADDRESS: u8 = 4;
+pub(crate) const GET_TX_HASH: u8 = 5;
+pub(crate) const GET_TX_TIME: u8 = 6;
+pub(crate) const GET_SIGNERS: u8 = 7;
+pub(crate) const REGISTER_USER: u8 = 8;
+pub(crate) const GET_USER: u8 = 9;
+pub(crate) const REGISTER_DATA_ACCOUNT: u8 = 10;
+pub(crate) const GET_DATA_ACCOUNT: u8 = 11;
+pub(crate) const SET_TEXT: u8 = 12;
+pub(crate) const SET_TEXT_WITH_VERSION: u8 = 13;
+pub(crate) const SET_INT64: u8 = 14;
+pub(crate) const SET_INT64_WITH_VERSION: u8 = 15;
+pub(crate) const GET_VALUE_VERSION: u8 = 16;
+pub(crate) const GET_VALUE: u8 = 17;
+pub(crate) const SET_BYTES: u8 = 18;
+pub(crate) const SET_BYTES_WITH_VERSION: u8 = 19;
+pub(crate) const SET_VALUE: u8 = 20;
+pub(crate) const SET_VALUE_WITH_VERSION: u8 = 21;
+pub(crate) const REGISTER_USER_WITH_PUBKEY: u8 = 22;
+pub(crate) const REGISTER_DATA_ACCOUNT_WITH_PUBKEY: u8 = 23;
+pub(crate) const REGISTER_EVENT_ACCOUNT: u8 = 24;
+pub(crate) const PUBLISH_EVENT: u8 = 25;
+pub(crate) const GET_LATEST_EVENT: u8 = 26;
+pub(crate) const INVOKE_CONTRACT: u8 = 27;
+pub(crate) const GET_ROLE_PRIVILEGE: u8 = 28;
+pub(crate) const GET_USER_ROLES: u8 = 29;
+pub(crate) const GET_USER_PRIVILEGE: u8 = 30;
+pub(crate) const CONFIGURE_ROLE: u8 = 31;
+pub(crate) const AUTHORIZE_USER_ROLES: u8 = 32;
+pub(crate) const GET_LATEST_BLOCK_HEIGHT: u8 = 33;
+pub(crate) const GET_LATEST_BLOCK_HASH: u8 = 34;
+pub(crate) const GET_PARTICIPANTS: u8 = 35;
+pub(crate) const GET_LEDGER_SETTINGS: u8 = 36;
+pub(crate) const GET_DATA_ENTRIES: u8 = 37;
+pub(crate) const GET_DATA_ENTRIES_TOTAL_COUNT: u8 = 38;
 
 // log level
-const LEVEL_DEBUG: u8 = 1;
-const LEVEL_INFO: u8 = 2;
-const LEVEL_ERROR: u8 = 3;
+pub(crate) const LEVEL_DEBUG: u8 = 1;
+pub(crate) const LEVEL_INFO: u8 = 2;
+pub(crate) const LEVEL_ERROR: u8 = 3;
 
-// value type
-// const TEXT: u8 = 1;
-// const INT64: u8 = 2;
+// value type，以类型名称传输，与 JD Chain DataType 对应
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ValueType {
+    #[serde(rename = "TEXT")]
+    Text,
+    #[serde(rename = "INT64")]
+    Int64,
+    #[serde(rename = "BYTES")]
+    Bytes,
+    #[serde(rename = "JSON")]
+    Json,
+    #[serde(rename = "XML")]
+    Xml,
+    #[serde(rename = "TIMESTAMP")]
+    Timestamp,
+    #[serde(rename = "IMG")]
+    Image,
+    #[serde(rename = "BIG_INT")]
+    BigInt,
+}
+
+impl ValueType {
+    // 是否以二进制载荷传输
+    pub fn is_binary(&self) -> bool {
+        matches!(self, ValueType::Bytes | ValueType::Image)
+    }
+}
+
+// KV 数据值
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DataValue {
+    // 文本
+    Text(String),
+    // 64 位整数
+    Int64(i64),
+    // 字节
+    Bytes(Vec<u8>),
+    // JSON 文本
+    Json(String),
+    // XML 文本
+    Xml(String),
+    // 时间戳，毫秒
+    Timestamp(i64),
+    // 图片
+    Image(Vec<u8>),
+    // 大整数，十进制文本
+    BigInt(String),
+}
+
+impl DataValue {
+    pub fn text<S: Into<String>>(value: S) -> Self {
+        DataValue::Text(value.into())
+    }
+
+    pub fn int64(value: i64) -> Self {
+        DataValue::Int64(value)
+    }
+
+    pub fn bytes<B: Into<Vec<u8>>>(value: B) -> Self {
+        DataValue::Bytes(value.into())
+    }
+
+    // 序列化为 JSON 文本
+    pub fn json<T: Serialize + ?Sized>(value: &T) -> Result<Self, ContractError> {
+        serde_json::to_string(value)
+            .map(DataValue::Json)
+            .map_err(|e| ContractError::Encode(e.to_string()))
+    }
+
+    pub fn xml<S: Into<String>>(value: S) -> Self {
+        DataValue::Xml(value.into())
+    }
+
+    pub fn timestamp(millis: i64) -> Self {
+        DataValue::Timestamp(millis)
+    }
+
+    pub fn image<B: Into<Vec<u8>>>(value: B) -> Self {
+        DataValue::Image(value.into())
+    }
+
+    // 十进制大整数，可带负号
+    pub fn big_int<S: Into<String>>(value: S) -> Result<Self, ContractError> {
+        let value = value.into();
+        if !is_big_int(&value) {
+            return Err(ContractError::Encode(format!("invalid big integer {}", value)));
+        }
+        Ok(DataValue::BigInt(value))
+    }
+
+    pub fn value_type(&self) -> ValueType {
+        match self {
+            DataValue::Text(_) => ValueType::Text,
+            DataValue::Int64(_) => ValueType::Int64,
+            DataValue::Bytes(_) => ValueType::Bytes,
+            DataValue::Json(_) => ValueType::Json,
+            DataValue::Xml(_) => ValueType::Xml,
+            DataValue::Timestamp(_) => ValueType::Timestamp,
+            DataValue::Image(_) => ValueType::Image,
+            DataValue::BigInt(_) => ValueType::BigInt,
+        }
+    }
+
+    // 文本类数据（TEXT/JSON/XML/BIG_INT）
+    pub fn as_text(&self) -> Option<&str> {
+        match self {
+            DataValue::Text(v) | DataValue::Json(v) | DataValue::Xml(v) | DataValue::BigInt(v) => Some(v),
+            _ => None,
+        }
+    }
+
+    // 数值类数据（INT64/TIMESTAMP）
+    pub fn as_int64(&self) -> Option<i64> {
+        match self {
+            DataValue::Int64(v) | DataValue::Timestamp(v) => Some(*v),
+            _ => None,
+        }
+    }
+
+    // 二进制数据（BYTES/IMG）
+    pub fn as_bytes(&self) -> Option<&[u8]> {
+        match self {
+            DataValue::Bytes(v) | DataValue::Image(v) => Some(v),
+            _ => None,
+        }
+    }
+
+    // 反序列化 JSON 数据
+    pub fn to_json<T: DeserializeOwned>(&self) -> Result<T, ContractError> {
+        match self {
+            DataValue::Json(v) => Ok(serde_json::from_str(v)?),
+            other => Err(ContractError::Decode(format!("expect JSON value but got {:?}", other.value_type()))),
+        }
+    }
+
+    // 转换为传输格式：类型、文本值、二进制载荷
+    pub(crate) fn into_wire(self) -> (ValueType, Option<String>, Option<Vec<u8>>) {
+        let value_type = self.value_type();
+        match self {
+            DataValue::Text(v) | DataValue::Json(v) | DataValue::Xml(v) | DataValue::BigInt(v) => (value_type, Some(v), None),
+            DataValue::Int64(v) | DataValue::Timestamp(v) => (value_type, Some(v.to_string()), None),
+            DataValue::Bytes(v) | DataValue::Image(v) => (value_type, None, Some(v)),
+        }
+    }
+
+    // 由传输格式解析
+    pub(crate) fn from_wire(value_type: ValueType, text: Option<String>, payload: Option<Vec<u8>>) -> Result<Self, ContractError> {
+        let int64 = |text: Option<String>| -> Result<i64, ContractError> {
+            let text = required(text, "v")?;
+            text.parse().map_err(|_| ContractError::Decode(format!("invalid {:?} value {}", value_type, text)))
+        };
+        Ok(match value_type {
+            ValueType::Text => DataValue::Text(required(text, "v")?),
+            ValueType::Int64 => DataValue::Int64(int64(text)?),
+            ValueType::Bytes => DataValue::Bytes(required(payload, "payload")?),
+            ValueType::Json => DataValue::Json(required(text, "v")?),
+            ValueType::Xml => DataValue::Xml(required(text, "v")?),
+            ValueType::Timestamp => DataValue::Timestamp(int64(text)?),
+            ValueType::Image => DataValue::Image(required(payload, "payload")?),
+            ValueType::BigInt => {
+                let text = required(text, "v")?;
+                if !is_big_int(&text) {
+                    return Err(ContractError::Decode(format!("invalid {:?} value {}", value_type, text)));
+                }
+                DataValue::BigInt(text)
+            }
+        })
+    }
+}
+
+// 十进制整数文本，可带负号
+fn is_big_int(value: &str) -> bool {
+    let digits = value.strip_prefix('-').unwrap_or(value);
+    !digits.is_empty() && digits.bytes().all(|b| b.is_ascii_digit())
+}
+
+impl From<&str> for DataValue {
+    fn from(value: &str) -> Self {
+        DataValue::Text(value.to_string())
+    }
+}
+
+impl From<String> for DataValue {
+    fn from(value: String) -> Self {
+        DataValue::Text(value)
+    }
+}
+
+impl From<i64> for DataValue {
+    fn from(value: i64) -> Self {
+        DataValue::Int64(value)
+    }
+}
+
+impl From<Vec<u8>> for DataValue {
+    fn from(value: Vec<u8>) -> Self {
+        DataValue::Bytes(value)
+    }
+}
+
+impl From<&[u8]> for DataValue {
+    fn from(value: &[u8]) -> Self {
+        DataValue::Bytes(value.to_vec())
+    }
+}
+
+impl fmt::Display for DataValue {
+    // 文本及数值类数据输出原文，二进制数据输出十六进制
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DataValue::Text(v) | DataValue::Json(v) | DataValue::Xml(v) | DataValue::BigInt(v) => f.write_str(v),
+            DataValue::Int64(v) | DataValue::Timestamp(v) => write!(f, "{}", v),
+            DataValue::Bytes(v) | DataValue::Image(v) => v.iter().try_for_each(|b| write!(f, "{:02x}", b)),
+        }
+    }
+}
+
+// crypto algorithm codes，与 JD Chain CryptoAlgorithm 编码对应
+pub(crate) const ALGORITHM_ED25519: u16 = 0x4115;
+pub(crate) const ALGORITHM_ECDSA: u16 = 0x4116;
+pub(crate) const ALGORITHM_SM2: u16 = 0xC102;
+pub(crate) const ALGORITHM_RSA: u16 = 0xC117;
+pub(crate) const ALGORITHM_SHA256: u16 = 0x2018;
+pub(crate) const ALGORITHM_RIPEMD160: u16 = 0x2019;
+pub(crate) const ALGORITHM_SM3: u16 = 0x2003;
+
+// 注册用户及数据账户时使用的签名算法，以算法名称传输，默认 ED25519
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Algorithm {
+    #[default]
+    #[serde(rename = "ED25519")]
+    Ed25519,
+    #[serde(rename = "SM2")]
+    Sm2,
+    #[serde(rename = "ECDSA")]
+    Ecdsa,
+    #[serde(rename = "RSA")]
+    Rsa,
+}
+
+impl Algorithm {
+    // 算法编码
+    pub fn code(&self) -> u16 {
+        match self {
+            Algorithm::Ed25519 => ALGORITHM_ED25519,
+            Algorithm::Sm2 => ALGORITHM_SM2,
+            Algorithm::Ecdsa => ALGORITHM_ECDSA,
+            Algorithm::Rsa => ALGORITHM_RSA,
+        }
+    }
+
+    // 由算法编码解析，非上述签名算法时返回 None
+    pub fn from_code(code: u16) -> Option<Self> {
+        match code {
+            ALGORITHM_ED25519 => Some(Algorithm::Ed25519),
+            ALGORITHM_SM2 => Some(Algorithm::Sm2),
+            ALGORITHM_ECDSA => Some(Algorithm::Ecdsa),
+            ALGORITHM_RSA => Some(Algorithm::Rsa),
+            _ => None,
+        }
+    }
+
+    // 算法名称
+    pub fn name(&self) -> &'static str {
+        match self {
+            Algorithm::Ed25519 => "ED25519",
+            Algorithm::Sm2 => "SM2",
+            Algorithm::Ecdsa => "ECDSA",
+            Algorithm::Rsa => "RSA",
+        }
+    }
+}
+
+impl fmt::Display for Algorithm {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for Algorithm {
+    type Err = ContractError;
+
+    // 按算法名称解析，不区分大小写
+    fn from_str(name: &str) -> Result<Self, ContractError> {
+        [Algorithm::Ed25519, Algorithm::Sm2, Algorithm::Ecdsa, Algorithm::Rsa]
+            .iter()
+            .copied()
+            .find(|algorithm| algorithm.name().eq_ignore_ascii_case(name))
+            .ok_or_else(|| ContractError::Decode(format!("unsupported algorithm {}", name)))
+    }
+}
+
+// 账本权限，以权限名称传输，与 JD Chain LedgerPermission 对应
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum LedgerPermission {
+    // 配置角色
+    #[serde(rename = "CONFIGURE_ROLES")]
+    ConfigureRoles,
+    // 授权用户角色
+    #[serde(rename = "AUTHORIZE_USER_ROLES")]
+    AuthorizeUserRoles,
+    // 设置共识协议
+    #[serde(rename = "SET_CONSENSUS")]
+    SetConsensus,
+    // 设置密码体系
+    #[serde(rename = "SET_CRYPTO")]
+    SetCrypto,
+    // 注册参与方
+    #[serde(rename = "REGISTER_PARTICIPANT")]
+    RegisterParticipant,
+    // 注册用户
+    #[serde(rename = "REGISTER_USER")]
+    RegisterUser,
+    // 注册数据账户
+    #[serde(rename = "REGISTER_DATA_ACCOUNT")]
+    RegisterDataAccount,
+    // 注册合约
+    #[serde(rename = "REGISTER_CONTRACT")]
+    RegisterContract,
+    // 升级合约
+    #[serde(rename = "UPGRADE_CONTRACT")]
+    UpgradeContract,
+    // 设置用户属性
+    #[serde(rename = "SET_USER_ATTRIBUTES")]
+    SetUserAttributes,
+    // 写数据账户
+    #[serde(rename = "WRITE_DATA_ACCOUNT")]
+    WriteDataAccount,
+    // 参与方核准交易
+    #[serde(rename = "APPROVE_TX")]
+    ApproveTx,
+    // 参与方共识交易
+    #[serde(rename = "CONSENSUS_TX")]
+    ConsensusTx,
+    // 注册事件账户
+    #[serde(rename = "REGISTER_EVENT_ACCOUNT")]
+    RegisterEventAccount,
+    // 发布事件
+    #[serde(rename = "WRITE_EVENT_ACCOUNT")]
+    WriteEventAccount,
+    // 更新用户状态
+    #[serde(rename = "UPDATE_USER_STATE")]
+    UpdateUserState,
+    // 更新数据账户状态
+    #[serde(rename = "UPDATE_DATA_ACCOUNT_STATE")]
+    UpdateDataAccountState,
+    // 更新事件账户状态
+    #[serde(rename = "UPDATE_EVENT_ACCOUNT_STATE")]
+    UpdateEventAccountState,
+    // 更新合约状态
+    #[serde(rename = "UPDATE_CONTRACT_STATE")]
+    UpdateContractState,
+}
+
+// 交易权限，以权限名称传输，与 JD Chain TransactionPermission 对应
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum TransactionPermission {
+    // 交易中包含指令操作
+    #[serde(rename = "DIRECT_OPERATION")]
+    DirectOperation,
+    // 交易中包含合约操作
+    #[serde(rename = "CONTRACT_OPERATION")]
+    ContractOperation,
+}
+
+// 多角色权限合并策略，默认取并集
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum RolesPolicy {
+    // 具有任一角色的权限即可
+    #[default]
+    #[serde(rename = "UNION")]
+    Union,
+    // 须同时具有全部角色的权限
+    #[serde(rename = "INTERSECT")]
+    Intersect,
+}
+
+// 地址版本
+const ADDRESS_VERSION: u8 = 0x91;
+// 地址长度：版本(1) | 算法编码(2) | 公钥哈希(20) | 校验码(4)
+const ADDRESS_LEN: usize = 27;
+// 公钥类型标识
+const KEY_TYPE_PUBLIC: u8 = 0x01;
+
+fn sha256(data: &[u8]) -> [u8; 32] {
+    Sha256::digest(data).into()
+}
+
+// 地址校验码，取两次 SHA256 的前 4 字节
+fn checksum(data: &[u8]) -> [u8; 4] {
+    let hash = sha256(&sha256(data));
+    [hash[0], hash[1], hash[2], hash[3]]
+}
+
+fn decode_base58(text: &str) -> Result<Vec<u8>, ContractError> {
+    bs58::decode(text).into_vec().map_err(|e| ContractError::InvalidBase58(format!("{}: {}", text, e)))
+}
+
+fn algorithm_code(bytes: &[u8]) -> u16 {
+    u16::from_be_bytes([bytes[0], bytes[1]])
+}
+
+// Base58 编码类型的公共实现：文本与字节互转、serde 以 Base58 文本编解码
+macro_rules! impl_base58 {
+    ($($ty:ident),*) => {
+        $(
+            impl $ty {
+                pub fn as_str(&self) -> &str {
+                    &self.text
+                }
+
+                // 编码前的完整字节
+                pub fn as_bytes(&self) -> &[u8] {
+                    &self.bytes
+                }
+
+                fn from_bytes(bytes: Vec<u8>) -> Self {
+                    $ty { text: bs58::encode(&bytes).into_string(), bytes }
+                }
+            }
+
+            impl fmt::Display for $ty {
+                fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                    f.write_str(&self.text)
+                }
+            }
+
+            impl FromStr for $ty {
+                type Err = ContractError;
+
+                fn from_str(text: &str) -> Result<Self, ContractError> {
+                    $ty::parse(text)
+                }
+            }
+
+            impl Serialize for $ty {
+                fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                    serializer.serialize_str(&self.text)
+                }
+            }
+
+            impl<'de> Deserialize<'de> for $ty {
+                fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                    let text = String::deserialize(deserializer)?;
+                    $ty::parse(&text).map_err(de::Error::custom)
+                }
+            }
+        )*
+    };
+}
+
+// 账户地址，Base58 编码：版本 | 公钥算法编码 | RIPEMD160(SHA256(公钥)) | 校验码
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Address {
+    text: String,
+    bytes: Vec<u8>,
+}
+
+impl Address {
+    // 解析 Base58 地址并校验版本、长度及校验码
+    pub fn parse(text: &str) -> Result<Self, ContractError> {
+        let bytes = decode_base58(text)?;
+        if bytes.len() != ADDRESS_LEN || bytes[0] != ADDRESS_VERSION {
+            return Err(ContractError::InvalidBase58(format!("{} is not an address", text)));
+        }
+        let (body, sum) = bytes.split_at(ADDRESS_LEN - 4);
+        if checksum(body) != sum {
+            return Err(ContractError::InvalidBase58(format!("address {} checksum mismatch", text)));
+        }
+        Ok(Address { text: text.to_string(), bytes })
+    }
+
+    // 由公钥生成地址
+    pub fn from_pubkey(pubkey: &PubKey) -> Self {
+        let hash = Ripemd160::digest(sha256(pubkey.raw_key()));
+        let mut bytes = Vec::with_capacity(ADDRESS_LEN);
+        bytes.push(ADDRESS_VERSION);
+        bytes.extend_from_slice(&pubkey.algorithm().to_be_bytes());
+        bytes.extend_from_slice(&hash);
+        let sum = checksum(&bytes);
+        bytes.extend_from_slice(&sum);
+        Address::from_bytes(bytes)
+    }
+
+    // 公钥算法编码
+    pub fn algorithm(&self) -> u16 {
+        algorithm_code(&self.bytes[1..])
+    }
+
+    // 公钥签名算法，非已知签名算法时返回 None
+    pub fn signature_algorithm(&self) -> Option<Algorithm> {
+        Algorithm::from_code(self.algorithm())
+    }
+
+    // 公钥哈希
+    pub fn digest(&self) -> &[u8] {
+        &self.bytes[3..ADDRESS_LEN - 4]
+    }
+}
+
+// 哈希，Base58 编码：哈希算法编码 | 摘要
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct HashDigest {
+    text: String,
+    bytes: Vec<u8>,
+}
+
+impl HashDigest {
+    // 解析 Base58 哈希，已知算法校验摘要长度
+    pub fn parse(text: &str) -> Result<Self, ContractError> {
+        let bytes = decode_base58(text)?;
+        if bytes.len() <= 2 {
+            return Err(ContractError::InvalidBase58(format!("{} is not a hash digest", text)));
+        }
+        let expected = match algorithm_code(&bytes) {
+            ALGORITHM_SHA256 | ALGORITHM_SM3 => Some(32),
+            ALGORITHM_RIPEMD160 => Some(20),
+            _ => None,
+        };
+        if expected.is_some_and(|len| len != bytes.len() - 2) {
+            return Err(ContractError::InvalidBase58(format!("hash digest {} has invalid length", text)));
+        }
+        Ok(HashDigest { text: text.to_string(), bytes })
+    }
+
+    // 计算 SHA256 哈希
+    pub fn sha256(data: &[u8]) -> Self {
+        let mut bytes = ALGORITHM_SHA256.to_be_bytes().to_vec();
+        bytes.extend_from_slice(&sha256(data));
+        HashDigest::from_bytes(bytes)
+    }
+
+    // 哈希算法编码
+    pub fn algorithm(&self) -> u16 {
+        algorithm_code(&self.bytes)
+    }
+
+    // 摘要
+    pub fn digest(&self) -> &[u8] {
+        &self.bytes[2..]
+    }
+}
+
+// 公钥，Base58 编码：签名算法编码 | 密钥类型 | 原始公钥
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct PubKey {
+    text: String,
+    bytes: Vec<u8>,
+}
+
+impl PubKey {
+    // 解析 Base58 公钥并校验密钥类型
+    pub fn parse(text: &str) -> Result<Self, ContractError> {
+        let bytes = decode_base58(text)?;
+        if bytes.len() <= 3 || bytes[2] != KEY_TYPE_PUBLIC {
+            return Err(ContractError::InvalidBase58(format!("{} is not a public key", text)));
+        }
+        Ok(PubKey { text: text.to_string(), bytes })
+    }
+
+    // 由算法编码及原始公钥构建
+    pub fn new(algorithm: u16, raw_key: &[u8]) -> Self {
+        let mut bytes = algorithm.to_be_bytes().to_vec();
+        bytes.push(KEY_TYPE_PUBLIC);
+        bytes.extend_from_slice(raw_key);
+        PubKey::from_bytes(bytes)
+    }
+
+    // 签名算法编码
+    pub fn algorithm(&self) -> u16 {
+        algorithm_code(&self.bytes)
+    }
+
+    // 签名算法，非已知签名算法时返回 None
+    pub fn signature_algorithm(&self) -> Option<Algorithm> {
+        Algorithm::from_code(self.algorithm())
+    }
+
+    // 原始公钥
+    pub fn raw_key(&self) -> &[u8] {
+        &self.bytes[3..]
+    }
+
+    // 对应的账户地址
+    pub fn address(&self) -> Address {
+        Address::from_pubkey(self)
+    }
+}
+
+impl_base58!(Address, HashDigest, PubKey);
+
+// 二进制帧标识，JSON 消息不会以该字节开头
+const FRAME_MARK: u8 = 0;
+
+// 运行时数据交互 - 消息
+// 仅含 JSON 头部时直接传输 JSON；携带二进制载荷时按帧传输：
+// FRAME_MARK | 头部长度(u32 LE) | JSON 头部 | 载荷长度(u32 LE) | 载荷
+pub struct Message {
+    // JSON 头部
+    pub header: String,
+    // 二进制载荷
+    pub payload: Option<Vec<u8>>,
+}
+
+impl Message {
+    pub fn json(header: String) -> Self {
+        Message { header, payload: None }
+    }
+
+    pub fn with_payload(header: String, payload: Vec<u8>) -> Self {
+        Message { header, payload: Some(payload) }
+    }
+
+    pub fn encode(self) -> Vec<u8> {
+        let payload = match self.payload {
+            Some(payload) => payload,
+            None => return self.header.into_bytes(),
+        };
+        let mut frame = Vec::with_capacity(9 + self.header.len() + payload.len());
+        frame.push(FRAME_MARK);
+        frame.extend_from_slice(&(self.header.len() as u32).to_le_bytes());
+        frame.extend_from_slice(self.header.as_bytes());
+        frame.extend_from_slice(&(payload.len() as u32).to_le_bytes());
+        frame.extend_from_slice(&payload);
+        frame
+    }
+
+    pub fn decode(mut data: Vec<u8>) -> Result<Self, ContractError> {
+        if data.first() != Some(&FRAME_MARK) {
+            // 兼容宿主将 JSON 消息结尾 NUL 计入长度
+            if data.last() == Some(&0) {
+                data.pop();
+            }
+            return Ok(Message::json(String::from_utf8(data)?));
+        }
+        let mut rest = &data[1..];
+        let header = take_chunk(&mut rest)?.to_vec();
+        let payload = take_chunk(&mut rest)?.to_vec();
+        if !rest.is_empty() {
+            return Err(ContractError::Decode(format!("{} trailing bytes in frame", rest.len())));
+        }
+        Ok(Message::with_payload(String::from_utf8(header)?, payload))
+    }
+}
+
+// 读取一段带长度前缀的数据
+fn take_chunk<'a>(data: &mut &'a [u8]) -> Result<&'a [u8], ContractError> {
+    if data.len() < 4 {
+        return Err(ContractError::Decode("truncated frame length".to_string()));
+    }
+    let (len, rest) = data.split_at(4);
+    let len = u32::from_le_bytes([len[0], len[1], len[2], len[3]]) as usize;
+    if rest.len() < len {
+        return Err(ContractError::Decode(format!("frame chunk of {} bytes truncated to {}", len, rest.len())));
+    }
+    let (chunk, rest) = rest.split_at(len);
+    *data = rest;
+    Ok(chunk)
+}
 
 // 运行时数据交互 - 请求
 #[derive(Serialize, Deserialize)]
 pub struct Request {
     // 请求类型，与 request types 对应
-    rt: u8,
+    pub(crate) rt: u8,
 }
 
 // 日志请求
 #[derive(Serialize, Deserialize)]
-struct LogRequest {
+pub(crate) struct LogRequest {
     // 请求类型，LEVEL_INFO
-    rt: u8,
+    pub(crate) rt: u8,
     // 等级 LEVEL_DEBUG/LEVEL_INFO/LEVEL_ERROR
-    l: u8,
+    pub(crate) l: u8,
     // 内容
-    m: String,
+    pub(crate) m: String,
 }
 
 // 注册用户请求
 #[derive(Serialize, Deserialize)]
-struct RegisterUserRequest {
+pub(crate) struct RegisterUserRequest {
     // 请求类型，REGISTER_USER
-    rt: u8,
+    pub(crate) rt: u8,
     // seed
-    s: String,
+    pub(crate) s: String,
     // algorithm
-    a: String,
+    pub(crate) a: Algorithm,
+}
+
+// 以公钥注册用户请求，私钥无需上链
+#[derive(Serialize, Deserialize)]
+pub(crate) struct RegisterUserWithPubkeyRequest {
+    // 请求类型，REGISTER_USER_WITH_PUBKEY
+    pub(crate) rt: u8,
+    // pubkey
+    pub(crate) pk: String,
 }
 
 // 查询用户请求
 #[derive(Serialize, Deserialize)]
-struct GetUserRequest {
+pub(crate) struct GetUserRequest {
     // 请求类型，GET_USER
-    rt: u8,
+    pub(crate) rt: u8,
     // address
-    a: String,
+    pub(crate) a: String,
 }
 
 // 注册数据账户请求
 #[derive(Serialize, Deserialize)]
-struct RegisterDataAccountRequest {
+pub(crate) struct RegisterDataAccountRequest {
     // 请求类型，REGISTER_DATA_ACCOUNT
-    rt: u8,
+    pub(crate) rt: u8,
     // seed
-    s: String,
+    pub(crate) s: String,
     // algorithm
-    a: String,
+    pub(crate) a: Algorithm,
+}
+
+// 以公钥注册数据账户请求，私钥无需上链
+#[derive(Serialize, Deserialize)]
+pub(crate) struct RegisterDataAccountWithPubkeyRequest {
+    // 请求类型，REGISTER_DATA_ACCOUNT_WITH_PUBKEY
+    pub(crate) rt: u8,
+    // pubkey
+    pub(crate) pk: String,
 }
 
 // 查询数据账户请求
 #[derive(Serialize, Deserialize)]
-struct GetDataAccountRequest {
+pub(crate) struct GetDataAccountRequest {
     // 请求类型，GET_DATA_ACCOUNT
-    rt: u8,
+    pub(crate) rt: u8,
     // address
-    a: String,
+    pub(crate) a: String,
 }
 
 // 写KV，字符类型，不带版本
 #[derive(Serialize, Deserialize)]
-struct SetTextRequest {
+pub(crate) struct SetTextRequest {
     // 请求类型，SET_TEXT
-    rt: u8,
+    pub(crate) rt: u8,
     // address
-    a: String,
+    pub(crate) a: String,
     // key
-    k: String,
+    pub(crate) k: String,
     // value
-    v: String,
+    pub(crate) v: String,
 }
 
 // 写KV，字符类型
 #[derive(Serialize, Deserialize)]
-struct SetTextWithVersionRequest {
+pub(crate) struct SetTextWithVersionRequest {
     // 请求类型，SET_TEXT_WITH_VERSION
-    rt: u8,
+    pub(crate) rt: u8,
     // address
-    a: String,
+    pub(crate) a: String,
     // key
-    k: String,
+    pub(crate) k: String,
     // value
-    v: String,
+    pub(crate) v: String,
     // version
-    ver: i64,
+    pub(crate) ver: i64,
 }
 
 // 写KV，数值类型，不带版本
 #[derive(Serialize, Deserialize)]
-struct SetInt64Request {
+pub(crate) struct SetInt64Request {
     // 请求类型，SET_INT64
-    rt: u8,
+    pub(crate) rt: u8,
     // address
-    a: String,
+    pub(crate) a: String,
     // key
-    k: String,
+    pub(crate) k: String,
     // value
-    v: i64,
+    pub(crate) v: i64,
 }
 
 // 写KV，数值类型
 #[derive(Serialize, Deserialize)]
-struct SetInt64WithVersionRequest {
+pub(crate) struct SetInt64WithVersionRequest {
     // 请求类型，SET_INT64_WITH_VERSION
-    rt: u8,
+    pub(crate) rt: u8,
     // address
-    a: String,
+    pub(crate) a: String,
     // key
-    k: String,
+    pub(crate) k: String,
     // value
-    v: i64,
+    pub(crate) v: i64,
     // version
-    ver: i64,
+    pub(crate) ver: i64,
 }
 
 // 查询数据版本
 #[derive(Serialize, Deserialize)]
-struct GetValueVersionRequest {
+pub(crate) struct GetValueVersionRequest {
     // 请求类型，GET_VALUE_VERSION
-    rt: u8,
+    pub(crate) rt: u8,
+    // address
+    pub(crate) a: String,
+    // key
+    pub(crate) k: String,
+}
+
+// 写KV，字节类型，不带版本，数据以二进制载荷传输
+#[derive(Serialize, Deserialize)]
+pub(crate) struct SetBytesRequest {
+    // 请求类型，SET_BYTES
+    pub(crate) rt: u8,
+    // address
+    pub(crate) a: String,
+    // key
+    pub(crate) k: String,
+}
+
+// 写KV，字节类型，数据以二进制载荷传输
+#[derive(Serialize, Deserialize)]
+pub(crate) struct SetBytesWithVersionRequest {
+    // 请求类型，SET_BYTES_WITH_VERSION
+    pub(crate) rt: u8,
+    // address
+    pub(crate) a: String,
+    // key
+    pub(crate) k: String,
+    // version
+    pub(crate) ver: i64,
+}
+
+// 写KV，任意数据类型，不带版本，二进制类型数据以载荷传输
+#[derive(Serialize, Deserialize)]
+pub(crate) struct SetValueRequest {
+    // 请求类型，SET_VALUE
+    pub(crate) rt: u8,
+    // address
+    pub(crate) a: String,
+    // key
+    pub(crate) k: String,
+    // value type
+    pub(crate) t: ValueType,
+    // value，二进制类型时为空
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) v: Option<String>,
+}
+
+// 写KV，任意数据类型，二进制类型数据以载荷传输
+#[derive(Serialize, Deserialize)]
+pub(crate) struct SetValueWithVersionRequest {
+    // 请求类型，SET_VALUE_WITH_VERSION
+    pub(crate) rt: u8,
     // address
-    a: String,
+    pub(crate) a: String,
     // key
-    k: String,
+    pub(crate) k: String,
+    // value type
+    pub(crate) t: ValueType,
+    // value，二进制类型时为空
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) v: Option<String>,
+    // version
+    pub(crate) ver: i64,
 }
 
 // 查询数据
 #[derive(Serialize, Deserialize)]
-struct GetValueRequest {
+pub(crate) struct GetValueRequest {
     // 请求类型，GET_VALUE
-    rt: u8,
+    pub(crate) rt: u8,
     // address
-    a: String,
+    pub(crate) a: String,
     // key
-    k: String,
+    pub(crate) k: String,
     // version
-    ver: i64,
+    pub(crate) ver: i64,
+}
+
+// 注册事件账户请求
+#[derive(Serialize, Deserialize)]
+pub(crate) struct RegisterEventAccountRequest {
+    // 请求类型，REGISTER_EVENT_ACCOUNT
+    pub(crate) rt: u8,
+    // seed
+    pub(crate) s: String,
+    // algorithm
+    pub(crate) a: Algorithm,
+}
+
+// 发布事件请求，二进制类型内容以载荷传输
+#[derive(Serialize, Deserialize)]
+pub(crate) struct PublishEventRequest {
+    // 请求类型，PUBLISH_EVENT
+    pub(crate) rt: u8,
+    // event account address
+    pub(crate) a: String,
+    // topic
+    pub(crate) tp: String,
+    // content type
+    pub(crate) t: ValueType,
+    // content，二进制类型时为空
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) v: Option<String>,
+    // 该主题当前最新序号，主题下无事件时为 -1
+    pub(crate) seq: i64,
+}
+
+// 查询最新事件请求
+#[derive(Serialize, Deserialize)]
+pub(crate) struct GetLatestEventRequest {
+    // 请求类型，GET_LATEST_EVENT
+    pub(crate) rt: u8,
+    // event account address
+    pub(crate) a: String,
+    // topic
+    pub(crate) tp: String,
+}
+
+// 合约调用参数，二进制类型参数依次拼接为载荷传输
+#[derive(Serialize, Deserialize)]
+pub(crate) struct InvokeArg {
+    // value type
+    pub(crate) t: ValueType,
+    // value，二进制类型时为空
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) v: Option<String>,
+    // 二进制类型参数在载荷中的长度
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) l: Option<usize>,
+}
+
+impl InvokeArg {
+    // 编码参数列表，返回参数及拼接后的载荷
+    pub(crate) fn encode(args: Vec<DataValue>) -> (Vec<InvokeArg>, Option<Vec<u8>>) {
+        let mut payload: Option<Vec<u8>> = None;
+        let args = args
+            .into_iter()
+            .map(|arg| {
+                let (t, v, bytes) = arg.into_wire();
+                let l = bytes.map(|bytes| {
+                    payload.get_or_insert_with(Vec::new).extend_from_slice(&bytes);
+                    bytes.len()
+                });
+                InvokeArg { t, v, l }
+            })
+            .collect();
+        (args, payload)
+    }
+
+    // 解码参数列表，二进制类型参数按长度依次取自载荷
+    pub(crate) fn decode(args: Vec<InvokeArg>, payload: Option<Vec<u8>>) -> Result<Vec<DataValue>, ContractError> {
+        let payload = payload.unwrap_or_default();
+        let mut offset = 0;
+        let mut values = Vec::with_capacity(args.len());
+        for arg in args {
+            let bytes = slice_payload(&payload, &mut offset, arg.l)?;
+            values.push(DataValue::from_wire(arg.t, arg.v, bytes)?);
+        }
+        Ok(values)
+    }
+}
+
+// 自 offset 起按长度截取拼接载荷中的一段数据，len 为空时返回 None
+pub(crate) fn slice_payload(payload: &[u8], offset: &mut usize, len: Option<usize>) -> Result<Option<Vec<u8>>, ContractError> {
+    let len = match len {
+        Some(len) => len,
+        None => return Ok(None),
+    };
+    let end = offset
+        .checked_add(len)
+        .filter(|end| *end <= payload.len())
+        .ok_or_else(|| ContractError::Decode(format!("payload of {} bytes has no {} bytes at offset {}", payload.len(), len, offset)))?;
+    let bytes = &payload[*offset..end];
+    *offset = end;
+    Ok(Some(bytes.to_vec()))
+}
+
+// 调用合约请求
+#[derive(Serialize, Deserialize)]
+pub(crate) struct InvokeContractRequest {
+    // 请求类型，INVOKE_CONTRACT
+    pub(crate) rt: u8,
+    // contract address
+    pub(crate) a: String,
+    // method
+    pub(crate) m: String,
+    // arguments
+    pub(crate) ps: Vec<InvokeArg>,
+}
+
+// 查询角色权限请求
+#[derive(Serialize, Deserialize)]
+pub(crate) struct GetRolePrivilegeRequest {
+    // 请求类型，GET_ROLE_PRIVILEGE
+    pub(crate) rt: u8,
+    // role
+    pub(crate) r: String,
+}
+
+// 查询用户角色请求
+#[derive(Serialize, Deserialize)]
+pub(crate) struct GetUserRolesRequest {
+    // 请求类型，GET_USER_ROLES
+    pub(crate) rt: u8,
+    // user address
+    pub(crate) a: String,
+}
+
+// 查询用户权限请求
+#[derive(Serialize, Deserialize)]
+pub(crate) struct GetUserPrivilegeRequest {
+    // 请求类型，GET_USER_PRIVILEGE
+    pub(crate) rt: u8,
+    // user address
+    pub(crate) a: String,
+}
+
+// 配置角色请求，角色不存在时创建
+#[derive(Serialize, Deserialize)]
+pub(crate) struct ConfigureRoleRequest {
+    // 请求类型，CONFIGURE_ROLE
+    pub(crate) rt: u8,
+    // role
+    pub(crate) r: String,
+    // 启用的账本权限
+    pub(crate) el: Vec<LedgerPermission>,
+    // 禁用的账本权限
+    pub(crate) dl: Vec<LedgerPermission>,
+    // 启用的交易权限
+    pub(crate) et: Vec<TransactionPermission>,
+    // 禁用的交易权限
+    pub(crate) dt: Vec<TransactionPermission>,
+}
+
+// 授权用户角色请求
+#[derive(Serialize, Deserialize)]
+pub(crate) struct AuthorizeUserRolesRequest {
+    // 请求类型，AUTHORIZE_USER_ROLES
+    pub(crate) rt: u8,
+    // user address
+    pub(crate) a: String,
+    // 授予的角色
+    pub(crate) ar: Vec<String>,
+    // 撤销的角色
+    pub(crate) ur: Vec<String>,
+    // 多角色权限合并策略，为空时保持不变
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) p: Option<RolesPolicy>,
+}
+
+// 分页查询数据账户 KV 请求
+#[derive(Serialize, Deserialize)]
+pub(crate) struct GetDataEntriesRequest {
+    // 请求类型，GET_DATA_ENTRIES
+    pub(crate) rt: u8,
+    // data account address
+    pub(crate) a: String,
+    // 起始序号，按 key 首次写入顺序
+    pub(crate) f: u64,
+    // 最大返回数量
+    pub(crate) c: u64,
+}
+
+// 查询数据账户 KV 总数请求
+#[derive(Serialize, Deserialize)]
+pub(crate) struct GetDataEntriesTotalCountRequest {
+    // 请求类型，GET_DATA_ENTRIES_TOTAL_COUNT
+    pub(crate) rt: u8,
+    // data account address
+    pub(crate) a: String,
 }
 
 // 数据交互请求构建
@@ -211,14 +1164,20 @@ impl Request {
         serde_json::to_string(&Request { rt: GET_SIGNERS }).unwrap()
     }
 
-    pub fn register_user(seed: String) -> String {
-        serde_json::to_string(&RegisterUserRequest { rt: REGISTER_USER, s: seed, a: "ED25519".to_string() }).unwrap()
+    pub fn register_user(seed: String, algorithm: Algorithm) -> String {
+        serde_json::to_string(&RegisterUserRequest { rt: REGISTER_USER, s: seed, a: algorithm }).unwrap()
+    }
+    pub fn register_user_with_pubkey(pubkey: String) -> String {
+        serde_json::to_string(&RegisterUserWithPubkeyRequest { rt: REGISTER_USER_WITH_PUBKEY, pk: pubkey }).unwrap()
     }
     pub fn get_user(address: String) -> String {
         serde_json::to_string(&GetUserRequest { rt: GET_USER, a: address }).unwrap()
     }
-    pub fn register_data_account(seed: String) -> String {
-        serde_json::to_string(&RegisterDataAccountRequest { rt: REGISTER_DATA_ACCOUNT, s: seed, a: "ED25519".to_string() }).unwrap()
+    pub fn register_data_account(seed: String, algorithm: Algorithm) -> String {
+        serde_json::to_string(&RegisterDataAccountRequest { rt: REGISTER_DATA_ACCOUNT, s: seed, a: algorithm }).unwrap()
+    }
+    pub fn register_data_account_with_pubkey(pubkey: String) -> String {
+        serde_json::to_string(&RegisterDataAccountWithPubkeyRequest { rt: REGISTER_DATA_ACCOUNT_WITH_PUBKEY, pk: pubkey }).unwrap()
     }
     pub fn get_data_account(address: String) -> String {
         serde_json::to_string(&GetDataAccountRequest { rt: GET_DATA_ACCOUNT, a: address }).unwrap()
@@ -238,53 +1197,379 @@ impl Request {
     pub fn get_value_version(address: String, key: String) -> String {
         serde_json::to_string(&GetValueVersionRequest { rt: GET_VALUE_VERSION, a: address, k: key }).unwrap()
     }
+    pub fn set_bytes(address: String, key: String) -> String {
+        serde_json::to_string(&SetBytesRequest { rt: SET_BYTES, a: address, k: key }).unwrap()
+    }
+    pub fn set_bytes_with_version(address: String, key: String, version: i64) -> String {
+        serde_json::to_string(&SetBytesWithVersionRequest { rt: SET_BYTES_WITH_VERSION, a: address, k: key, ver: version }).unwrap()
+    }
+    pub fn set_value(address: String, key: String, value_type: ValueType, value: Option<String>) -> String {
+        serde_json::to_string(&SetValueRequest { rt: SET_VALUE, a: address, k: key, t: value_type, v: value }).unwrap()
+    }
+    pub fn set_value_with_version(address: String, key: String, value_type: ValueType, value: Option<String>, version: i64) -> String {
+        serde_json::to_string(&SetValueWithVersionRequest { rt: SET_VALUE_WITH_VERSION, a: address, k: key, t: value_type, v: value, ver: version }).unwrap()
+    }
     pub fn get_value(address: String, key: String, version: i64) -> String {
         serde_json::to_string(&GetValueRequest { rt: GET_VALUE, a: address, k: key, ver: version }).unwrap()
     }
+    pub fn register_event_account(seed: String, algorithm: Algorithm) -> String {
+        serde_json::to_string(&RegisterEventAccountRequest { rt: REGISTER_EVENT_ACCOUNT, s: seed, a: algorithm }).unwrap()
+    }
+    pub fn publish_event(address: String, topic: String, content_type: ValueType, content: Option<String>, sequence: i64) -> String {
+        serde_json::to_string(&PublishEventRequest { rt: PUBLISH_EVENT, a: address, tp: topic, t: content_type, v: content, seq: sequence }).unwrap()
+    }
+    pub fn get_latest_event(address: String, topic: String) -> String {
+        serde_json::to_string(&GetLatestEventRequest { rt: GET_LATEST_EVENT, a: address, tp: topic }).unwrap()
+    }
+    pub(crate) fn invoke_contract(address: String, method: String, args: Vec<InvokeArg>) -> String {
+        serde_json::to_string(&InvokeContractRequest { rt: INVOKE_CONTRACT, a: address, m: method, ps: args }).unwrap()
+    }
+    pub fn get_role_privilege(role: String) -> String {
+        serde_json::to_string(&GetRolePrivilegeRequest { rt: GET_ROLE_PRIVILEGE, r: role }).unwrap()
+    }
+    pub fn get_user_roles(address: String) -> String {
+        serde_json::to_string(&GetUserRolesRequest { rt: GET_USER_ROLES, a: address }).unwrap()
+    }
+    pub fn get_user_privilege(address: String) -> String {
+        serde_json::to_string(&GetUserPrivilegeRequest { rt: GET_USER_PRIVILEGE, a: address }).unwrap()
+    }
+    pub fn configure_role(config: &RoleConfig) -> String {
+        serde_json::to_string(&ConfigureRoleRequest {
+            rt: CONFIGURE_ROLE,
+            r: config.role.clone(),
+            el: config.enable_ledger.clone(),
+            dl: config.disable_ledger.clone(),
+            et: config.enable_transaction.clone(),
+            dt: config.disable_transaction.clone(),
+        }).unwrap()
+    }
+    pub fn authorize_user_roles(address: String, authorize: Vec<String>, unauthorize: Vec<String>, policy: Option<RolesPolicy>) -> String {
+        serde_json::to_string(&AuthorizeUserRolesRequest { rt: AUTHORIZE_USER_ROLES, a: address, ar: authorize, ur: unauthorize, p: policy }).unwrap()
+    }
+    pub fn get_latest_block_height() -> String {
+        serde_json::to_string(&Request { rt: GET_LATEST_BLOCK_HEIGHT }).unwrap()
+    }
+    pub fn get_latest_block_hash() -> String {
+        serde_json::to_string(&Request { rt: GET_LATEST_BLOCK_HASH }).unwrap()
+    }
+    pub fn get_participants() -> String {
+        serde_json::to_string(&Request { rt: GET_PARTICIPANTS }).unwrap()
+    }
+    pub fn get_ledger_settings() -> String {
+        serde_json::to_string(&Request { rt: GET_LEDGER_SETTINGS }).unwrap()
+    }
+    pub fn get_data_entries(address: String, from_index: u64, count: u64) -> String {
+        serde_json::to_string(&GetDataEntriesRequest { rt: GET_DATA_ENTRIES, a: address, f: from_index, c: count }).unwrap()
+    }
+    pub fn get_data_entries_total_count(address: String) -> String {
+        serde_json::to_string(&GetDataEntriesTotalCountRequest { rt: GET_DATA_ENTRIES_TOTAL_COUNT, a: address }).unwrap()
+    }
 }
 
+// result codes
+#[deprecated(note = "use `ResultCode::Success` instead")]
 pub const SUCCESS: u8 = 0;
+#[deprecated(note = "use `ResultCode::Error` instead")]
 pub const ERROR: u8 = 1;
+const NOT_FOUND: u8 = 2;
+const ILLEGAL_ARGUMENT: u8 = 3;
+const UNAUTHORIZED: u8 = 4;
+const UNSUPPORTED: u8 = 5;
+
+// 响应编码
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(from = "u8", into = "u8")]
+pub enum ResultCode {
+    // 成功
+    Success,
+    // 执行出错
+    Error,
+    // 账户或数据不存在
+    NotFound,
+    // 请求参数错误
+    IllegalArgument,
+    // 无操作权限
+    Unauthorized,
+    // 不支持的请求类型
+    Unsupported,
+    // 未定义的响应编码
+    Unknown(u8),
+}
+
+impl From<u8> for ResultCode {
+    fn from(code: u8) -> Self {
+        match code {
+            #[allow(deprecated)]
+            SUCCESS => ResultCode::Success,
+            #[allow(deprecated)]
+            ERROR => ResultCode::Error,
+            NOT_FOUND => ResultCode::NotFound,
+            ILLEGAL_ARGUMENT => ResultCode::IllegalArgument,
+            UNAUTHORIZED => ResultCode::Unauthorized,
+            UNSUPPORTED => ResultCode::Unsupported,
+            _ => ResultCode::Unknown(code),
+        }
+    }
+}
+
+impl From<ResultCode> for u8 {
+    fn from(code: ResultCode) -> Self {
+        match code {
+            #[allow(deprecated)]
+            ResultCode::Success => SUCCESS,
+            #[allow(deprecated)]
+            ResultCode::Error => ERROR,
+            ResultCode::NotFound => NOT_FOUND,
+            ResultCode::IllegalArgument => ILLEGAL_ARGUMENT,
+            ResultCode::Unauthorized => UNAUTHORIZED,
+            ResultCode::Unsupported => UNSUPPORTED,
+            ResultCode::Unknown(code) => code,
+        }
+    }
+}
+
+// error codes，响应编码的细分错误原因
+const VERSION_CONFLICT: u16 = 1;
+const ACCOUNT_NOT_FOUND: u16 = 2;
+const ACCOUNT_EXISTS: u16 = 3;
+const KEY_NOT_FOUND: u16 = 4;
+const PERMISSION_DENIED: u16 = 5;
+const INVALID_VALUE: u16 = 6;
+
+// 错误细分编码
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(from = "u16", into = "u16")]
+pub enum ErrorCode {
+    // 数据版本冲突
+    VersionConflict,
+    // 账户不存在
+    AccountNotFound,
+    // 账户已存在
+    AccountExists,
+    // 数据键不存在
+    KeyNotFound,
+    // 权限不足
+    PermissionDenied,
+    // 数据值非法
+    InvalidValue,
+    // 未定义的错误编码
+    Unknown(u16),
+}
+
+impl From<u16> for ErrorCode {
+    fn from(code: u16) -> Self {
+        match code {
+            VERSION_CONFLICT => ErrorCode::VersionConflict,
+            ACCOUNT_NOT_FOUND => ErrorCode::AccountNotFound,
+            ACCOUNT_EXISTS => ErrorCode::AccountExists,
+            KEY_NOT_FOUND => ErrorCode::KeyNotFound,
+            PERMISSION_DENIED => ErrorCode::PermissionDenied,
+            INVALID_VALUE => ErrorCode::InvalidValue,
+            _ => ErrorCode::Unknown(code),
+        }
+    }
+}
+
+impl From<ErrorCode> for u16 {
+    fn from(code: ErrorCode) -> Self {
+        match code {
+            ErrorCode::VersionConflict => VERSION_CONFLICT,
+            ErrorCode::AccountNotFound => ACCOUNT_NOT_FOUND,
+            ErrorCode::AccountExists => ACCOUNT_EXISTS,
+            ErrorCode::KeyNotFound => KEY_NOT_FOUND,
+            ErrorCode::PermissionDenied => PERMISSION_DENIED,
+            ErrorCode::InvalidValue => INVALID_VALUE,
+            ErrorCode::Unknown(code) => code,
+        }
+    }
+}
+
+// 宿主返回的错误信息
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HostError {
+    // 响应编码
+    pub code: ResultCode,
+    // 错误细分编码
+    pub sub_code: Option<ErrorCode>,
+    // 错误描述
+    pub message: Option<String>,
+}
+
+impl fmt::Display for HostError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "host returned {:?}", self.code)?;
+        if let Some(sub_code) = self.sub_code {
+            write!(f, " ({:?})", sub_code)?;
+        }
+        if let Some(message) = &self.message {
+            write!(f, ": {}", message)?;
+        }
+        Ok(())
+    }
+}
+
+// 合约错误
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ContractError {
+    // 宿主返回非成功响应
+    Host(HostError),
+    // 返回数据解析失败
+    Decode(String),
+    // 输入不是合法的 UTF-8 字符串
+    InvalidUtf8,
+    // 运行时传入空指针
+    NullPointer,
+    // 返回数据编码失败
+    Encode(String),
+    // 返回数据缺少必要字段
+    MissingField(&'static str),
+    // 存储集合下标越界
+    IndexOutOfBounds { index: u64, len: u64 },
+    // 带版本写入时数据已被修改，version 为写入时指定的版本
+    VersionConflict { key: String, version: i64 },
+    // 地址、哈希或公钥不是合法的 Base58 编码
+    InvalidBase58(String),
+    // 交易签名用户中不包含指定地址
+    MissingSigner(Address),
+    // 指定地址中参与签名的数量未达到阈值
+    InsufficientSigners { required: usize, signed: usize },
+    // 交易签名用户均不具有指定角色
+    MissingRole(String),
+    // 合约所有者未设置
+    OwnerNotSet,
+    // 合约所有者已设置
+    OwnerAlreadySet(Address),
+}
+
+impl fmt::Display for ContractError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ContractError::Host(err) => err.fmt(f),
+            ContractError::Decode(msg) => write!(f, "decode response failed: {}", msg),
+            ContractError::InvalidUtf8 => write!(f, "invalid utf-8 input"),
+            ContractError::NullPointer => write!(f, "null pointer input"),
+            ContractError::Encode(msg) => write!(f, "encode result failed: {}", msg),
+            ContractError::MissingField(field) => write!(f, "missing field `{}` in response", field),
+            ContractError::IndexOutOfBounds { index, len } => write!(f, "index {} out of bounds for length {}", index, len),
+            ContractError::VersionConflict { key, version } => write!(f, "version conflict on key {}, expected version {}", key, version),
+            ContractError::InvalidBase58(msg) => write!(f, "invalid base58: {}", msg),
+            ContractError::MissingSigner(address) => write!(f, "{} is not a signer of the transaction", address),
+            ContractError::InsufficientSigners { required, signed } => write!(f, "{} of required {} signers signed the transaction", signed, required),
+            ContractError::MissingRole(role) => write!(f, "no signer of the transaction has role {}", role),
+            ContractError::OwnerNotSet => write!(f, "owner is not set"),
+            ContractError::OwnerAlreadySet(owner) => write!(f, "owner is already set to {}", owner),
+        }
+    }
+}
+
+impl std::error::Error for ContractError {}
+
+impl ContractError {
+    // 宿主返回的响应编码
+    pub fn code(&self) -> Option<ResultCode> {
+        match self {
+            ContractError::Host(err) => Some(err.code),
+            ContractError::VersionConflict { .. } => Some(ResultCode::Error),
+            _ => None,
+        }
+    }
+
+    // 宿主返回的错误细分编码
+    pub fn sub_code(&self) -> Option<ErrorCode> {
+        match self {
+            ContractError::Host(err) => err.sub_code,
+            ContractError::VersionConflict { .. } => Some(ErrorCode::VersionConflict),
+            _ => None,
+        }
+    }
+
+    // 宿主返回的错误描述
+    pub fn message(&self) -> Option<&str> {
+        match self {
+            ContractError::Host(err) => err.message.as_deref(),
+            _ => None,
+        }
+    }
+}
+
+impl From<serde_json::Error> for ContractError {
+    fn from(err: serde_json::Error) -> Self {
+        ContractError::Decode(err.to_string())
+    }
+}
+
+impl From<Utf8Error> for ContractError {
+    fn from(_: Utf8Error) -> Self {
+        ContractError::InvalidUtf8
+    }
+}
+
+impl From<FromUtf8Error> for ContractError {
+    fn from(_: FromUtf8Error) -> Self {
+        ContractError::InvalidUtf8
+    }
+}
 
 // 运行时数据交互 - 返回
 #[derive(Serialize, Deserialize)]
-pub struct Result {
+pub struct Response {
     // 响应编码，与 result codes 对应
-    rc: u8,
+    pub rc: ResultCode,
+    // 错误细分编码，与 error codes 对应，仅失败时返回
+    #[serde(default)]
+    pub ec: Option<ErrorCode>,
+    // 错误描述，仅失败时返回
+    #[serde(default)]
+    pub em: Option<String>,
+}
+
+impl Response {
+    // 校验响应编码，非成功时返回宿主错误信息
+    pub fn check(self) -> Result<(), ContractError> {
+        match self.rc {
+            ResultCode::Success => Ok(()),
+            code => Err(ContractError::Host(HostError { code, sub_code: self.ec, message: self.em })),
+        }
+    }
+}
+
+// 校验返回必要字段
+pub fn required<T>(field: Option<T>, name: &'static str) -> Result<T, ContractError> {
+    field.ok_or(ContractError::MissingField(name))
 }
 
 // 获取账本哈希返回
 #[derive(Serialize, Deserialize)]
 pub struct GetLedgerHashResult {
     // 响应编码，与 result codes 对应
-    pub rc: u8,
+    pub rc: ResultCode,
     // 账本哈希
-    pub lh: Option<String>,
+    pub lh: Option<HashDigest>,
 }
 
 // 获取合约地址返回
 #[derive(Serialize, Deserialize)]
 pub struct GetContractAddressResult {
     // 响应编码，与 result codes 对应
-    pub rc: u8,
+    pub rc: ResultCode,
     // 合约地址
-    pub ca: Option<String>,
+    pub ca: Option<Address>,
 }
 
 // 获取交易哈希返回
 #[derive(Serialize, Deserialize)]
 pub struct GetTxHashResult {
     // 响应编码，与 result codes 对应
-    pub rc: u8,
+    pub rc: ResultCode,
     // 交易哈希
-    pub th: Option<String>,
+    pub th: Option<HashDigest>,
 }
 
 // 获取交易时间返回
 #[derive(Serialize, Deserialize)]
 pub struct GetTxTimeResult {
     // 响应编码，与 result codes 对应
-    pub rc: u8,
+    pub rc: ResultCode,
     // 交易哈希
     pub tt: Option<u64>,
 }
@@ -293,72 +1578,72 @@ pub struct GetTxTimeResult {
 #[derive(Serialize, Deserialize)]
 pub struct GetSignersResult {
     // 响应编码，与 result codes 对应
-    pub rc: u8,
+    pub rc: ResultCode,
     // 交易签名用户列表
-    pub ss: Option<Vec<String>>,
+    pub ss: Option<Vec<Address>>,
 }
 
 // 注册用户返回
 #[derive(Serialize, Deserialize)]
 pub struct RegisterUserResult {
     // 响应编码，与 result codes 对应
-    pub rc: u8,
+    pub rc: ResultCode,
     // 用户地址
-    pub a: Option<String>,
+    pub a: Option<Address>,
 }
 
 // 查询用户返回
 #[derive(Serialize, Deserialize)]
 pub struct GetUserResult {
     // 响应编码，与 result codes 对应
-    pub rc: u8,
+    pub rc: ResultCode,
     // 用户地址
-    pub a: Option<String>,
+    pub a: Option<Address>,
     // 用户公钥
-    pub pk: Option<String>,
+    pub pk: Option<PubKey>,
 }
 
 // 用户
 pub struct User {
     // 用户地址
-    pub address: String,
+    pub address: Address,
     // 用户公钥
-    pub pubkey: String,
+    pub pubkey: PubKey,
 }
 
 // 注册数据账户返回
 #[derive(Serialize, Deserialize)]
 pub struct RegisterDataAccountResult {
     // 响应编码，与 result codes 对应
-    pub rc: u8,
+    pub rc: ResultCode,
     // 数据账户地址
-    pub a: Option<String>,
+    pub a: Option<Address>,
 }
 
 // 查询数据账户返回
 #[derive(Serialize, Deserialize)]
 pub struct GetDataAccountResult {
     // 响应编码，与 result codes 对应
-    pub rc: u8,
+    pub rc: ResultCode,
     // 数据账户地址
-    pub a: Option<String>,
+    pub a: Option<Address>,
     // 数据账户公钥
-    pub pk: Option<String>,
+    pub pk: Option<PubKey>,
 }
 
 // 数据账户
 pub struct DataAccount {
     // 数据账户地址
-    pub address: String,
+    pub address: Address,
     // 数据账户公钥
-    pub pubkey: String,
+    pub pubkey: PubKey,
 }
 
 // 写 KV 返回
 #[derive(Serialize, Deserialize)]
 pub struct SetKVResult {
     // 响应编码，与 result codes 对应
-    pub rc: u8,
+    pub rc: ResultCode,
     // 数据版本
     pub ver: Option<i64>,
 }
@@ -367,22 +1652,23 @@ pub struct SetKVResult {
 #[derive(Serialize, Deserialize)]
 pub struct GetValueVersionResult {
     // 响应编码，与 result codes 对应
-    pub rc: u8,
+    pub rc: ResultCode,
     // 数据版本
     pub ver: Option<i64>,
 }
 
-// 查 KV 返回
+// 查 KV 返回，二进制类型数据以载荷返回
 #[derive(Serialize, Deserialize)]
 pub struct GetValueResult {
     // 响应编码，与 result codes 对应
-    pub rc: u8,
+    pub rc: ResultCode,
     // key
     pub k: Option<String>,
-    // value
+    // value，二进制类型时为空
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub v: Option<String>,
     // value type
-    pub t: Option<String>,
+    pub t: Option<ValueType>,
     // 数据版本
     pub ver: Option<i64>,
 }
@@ -392,9 +1678,410 @@ pub struct KVData {
     // key
     pub key: String,
     // value
-    pub value: String,
-    // value type
-    pub value_type: String,
+    pub value: DataValue,
     // 数据版本
     pub version: i64,
-}
\ No newline at end of file
+}
+
+// 字节类型KV数据
+pub struct BytesData {
+    // key
+    pub key: String,
+    // value
+    pub value: Vec<u8>,
+    // 数据版本
+    pub version: i64,
+}
+
+// 注册事件账户返回
+#[derive(Serialize, Deserialize)]
+pub struct RegisterEventAccountResult {
+    // 响应编码，与 result codes 对应
+    pub rc: ResultCode,
+    // 事件账户地址
+    pub a: Option<Address>,
+}
+
+// 发布事件返回
+#[derive(Serialize, Deserialize)]
+pub struct PublishEventResult {
+    // 响应编码，与 result codes 对应
+    pub rc: ResultCode,
+    // 发布后的事件序号
+    pub seq: Option<i64>,
+}
+
+// 查询最新事件返回，二进制类型内容以载荷返回
+#[derive(Serialize, Deserialize)]
+pub struct GetLatestEventResult {
+    // 响应编码，与 result codes 对应
+    pub rc: ResultCode,
+    // topic
+    pub tp: Option<String>,
+    // content，二进制类型时为空
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub v: Option<String>,
+    // content type
+    pub t: Option<ValueType>,
+    // 事件序号
+    pub seq: Option<i64>,
+    // 发布事件的交易哈希
+    pub th: Option<HashDigest>,
+}
+
+// 事件
+pub struct Event {
+    // 事件账户地址
+    pub account: Address,
+    // 主题
+    pub topic: String,
+    // 内容
+    pub content: DataValue,
+    // 事件序号，同一主题下从 0 开始递增
+    pub sequence: i64,
+    // 发布事件的交易哈希
+    pub tx_hash: HashDigest,
+}
+
+// 调用合约返回，二进制类型返回值以载荷返回
+#[derive(Serialize, Deserialize)]
+pub struct InvokeContractResult {
+    // 响应编码，与 result codes 对应
+    pub rc: ResultCode,
+    // value，二进制类型时为空
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub v: Option<String>,
+    // value type，合约方法无返回值时为空
+    pub t: Option<ValueType>,
+}
+
+// 合约方法返回值
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReturnValue {
+    value: Option<DataValue>,
+}
+
+impl ReturnValue {
+    pub fn new(value: Option<DataValue>) -> Self {
+        ReturnValue { value }
+    }
+
+    // 合约方法是否无返回值
+    pub fn is_empty(&self) -> bool {
+        self.value.is_none()
+    }
+
+    pub fn value(&self) -> Option<&DataValue> {
+        self.value.as_ref()
+    }
+
+    pub fn into_value(self) -> Option<DataValue> {
+        self.value
+    }
+
+    pub fn as_text(&self) -> Option<&str> {
+        self.value.as_ref().and_then(DataValue::as_text)
+    }
+
+    pub fn as_int64(&self) -> Option<i64> {
+        self.value.as_ref().and_then(DataValue::as_int64)
+    }
+
+    pub fn as_bytes(&self) -> Option<&[u8]> {
+        self.value.as_ref().and_then(DataValue::as_bytes)
+    }
+
+    // 以 JSON 解析返回值，无返回值时返回错误
+    pub fn to_json<T: DeserializeOwned>(&self) -> Result<T, ContractError> {
+        self.value.as_ref().ok_or(ContractError::MissingField("v"))?.to_json()
+    }
+}
+
+// 角色权限配置，按启用、禁用的账本权限及交易权限修改角色，角色不存在时创建
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RoleConfig {
+    pub(crate) role: String,
+    pub(crate) enable_ledger: Vec<LedgerPermission>,
+    pub(crate) disable_ledger: Vec<LedgerPermission>,
+    pub(crate) enable_transaction: Vec<TransactionPermission>,
+    pub(crate) disable_transaction: Vec<TransactionPermission>,
+}
+
+impl RoleConfig {
+    pub fn new<S: Into<String>>(role: S) -> Self {
+        RoleConfig {
+            role: role.into(),
+            enable_ledger: Vec::new(),
+            disable_ledger: Vec::new(),
+            enable_transaction: Vec::new(),
+            disable_transaction: Vec::new(),
+        }
+    }
+
+    pub fn role(&self) -> &str {
+        &self.role
+    }
+
+    // 启用账本权限
+    pub fn enable(mut self, permissions: &[LedgerPermission]) -> Self {
+        self.enable_ledger.extend_from_slice(permissions);
+        self
+    }
+
+    // 禁用账本权限
+    pub fn disable(mut self, permissions: &[LedgerPermission]) -> Self {
+        self.disable_ledger.extend_from_slice(permissions);
+        self
+    }
+
+    // 启用交易权限
+    pub fn enable_transaction(mut self, permissions: &[TransactionPermission]) -> Self {
+        self.enable_transaction.extend_from_slice(permissions);
+        self
+    }
+
+    // 禁用交易权限
+    pub fn disable_transaction(mut self, permissions: &[TransactionPermission]) -> Self {
+        self.disable_transaction.extend_from_slice(permissions);
+        self
+    }
+}
+
+// 查询角色权限返回
+#[derive(Serialize, Deserialize)]
+pub struct GetRolePrivilegeResult {
+    // 响应编码，与 result codes 对应
+    pub rc: ResultCode,
+    // 角色名称
+    pub r: Option<String>,
+    // 账本权限
+    pub lp: Option<Vec<LedgerPermission>>,
+    // 交易权限
+    pub tp: Option<Vec<TransactionPermission>>,
+}
+
+// 查询用户角色返回
+#[derive(Serialize, Deserialize)]
+pub struct GetUserRolesResult {
+    // 响应编码，与 result codes 对应
+    pub rc: ResultCode,
+    // 用户地址
+    pub a: Option<Address>,
+    // 角色名称列表
+    pub rs: Option<Vec<String>>,
+    // 多角色权限合并策略
+    pub p: Option<RolesPolicy>,
+}
+
+// 查询用户权限返回
+#[derive(Serialize, Deserialize)]
+pub struct GetUserPrivilegeResult {
+    // 响应编码，与 result codes 对应
+    pub rc: ResultCode,
+    // 用户地址
+    pub a: Option<Address>,
+    // 角色名称列表
+    pub rs: Option<Vec<String>>,
+    // 按合并策略计算后的账本权限
+    pub lp: Option<Vec<LedgerPermission>>,
+    // 按合并策略计算后的交易权限
+    pub tp: Option<Vec<TransactionPermission>>,
+}
+
+// 角色权限
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RolePrivilege {
+    // 角色名称
+    pub role: String,
+    pub ledger_permissions: Vec<LedgerPermission>,
+    pub transaction_permissions: Vec<TransactionPermission>,
+}
+
+impl RolePrivilege {
+    pub fn has_ledger_permission(&self, permission: LedgerPermission) -> bool {
+        self.ledger_permissions.contains(&permission)
+    }
+
+    pub fn has_transaction_permission(&self, permission: TransactionPermission) -> bool {
+        self.transaction_permissions.contains(&permission)
+    }
+}
+
+// 用户角色
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UserRoles {
+    // 用户地址
+    pub address: Address,
+    // 角色名称列表
+    pub roles: Vec<String>,
+    // 多角色权限合并策略
+    pub policy: RolesPolicy,
+}
+
+// 用户权限，由用户全部角色按合并策略计算
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UserPrivilege {
+    // 用户地址
+    pub address: Address,
+    // 角色名称列表
+    pub roles: Vec<String>,
+    pub ledger_permissions: Vec<LedgerPermission>,
+    pub transaction_permissions: Vec<TransactionPermission>,
+}
+
+impl UserPrivilege {
+    pub fn has_ledger_permission(&self, permission: LedgerPermission) -> bool {
+        self.ledger_permissions.contains(&permission)
+    }
+
+    pub fn has_transaction_permission(&self, permission: TransactionPermission) -> bool {
+        self.transaction_permissions.contains(&permission)
+    }
+}
+
+// 获取最新区块高度返回
+#[derive(Serialize, Deserialize)]
+pub struct GetLatestBlockHeightResult {
+    // 响应编码，与 result codes 对应
+    pub rc: ResultCode,
+    // 区块高度
+    pub h: Option<i64>,
+}
+
+// 获取最新区块哈希返回
+#[derive(Serialize, Deserialize)]
+pub struct GetLatestBlockHashResult {
+    // 响应编码，与 result codes 对应
+    pub rc: ResultCode,
+    // 区块哈希
+    pub bh: Option<HashDigest>,
+}
+
+// 参与方状态，以状态名称传输，与 JD Chain ParticipantNodeState 对应
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ParticipantState {
+    // 已注册，未参与共识
+    #[serde(rename = "READY")]
+    Ready,
+    // 参与共识
+    #[serde(rename = "CONSENSUS")]
+    Consensus,
+    // 已移除
+    #[serde(rename = "DEACTIVATED")]
+    Deactivated,
+}
+
+// 账本参与方
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct Participant {
+    // 参与方编号
+    pub id: i32,
+    // 参与方名称
+    #[serde(rename = "n")]
+    pub name: String,
+    // 参与方地址
+    #[serde(rename = "a")]
+    pub address: Address,
+    // 参与方公钥
+    #[serde(rename = "pk")]
+    pub pubkey: PubKey,
+    // 参与方状态
+    #[serde(rename = "st")]
+    pub state: ParticipantState,
+}
+
+// 获取参与方列表返回
+#[derive(Serialize, Deserialize)]
+pub struct GetParticipantsResult {
+    // 响应编码，与 result codes 对应
+    pub rc: ResultCode,
+    // 参与方列表
+    pub ps: Option<Vec<Participant>>,
+}
+
+// 密码算法
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct CryptoAlgorithm {
+    // 算法编码
+    #[serde(rename = "c")]
+    pub code: u16,
+    // 算法名称
+    #[serde(rename = "n")]
+    pub name: String,
+}
+
+impl CryptoAlgorithm {
+    pub fn new<S: Into<String>>(code: u16, name: S) -> Self {
+        CryptoAlgorithm { code, name: name.into() }
+    }
+}
+
+// 获取账本配置返回
+#[derive(Serialize, Deserialize)]
+pub struct GetLedgerSettingsResult {
+    // 响应编码，与 result codes 对应
+    pub rc: ResultCode,
+    // 共识服务提供者
+    pub cp: Option<String>,
+    // 哈希算法
+    pub ha: Option<CryptoAlgorithm>,
+    // 支持的密码算法
+    pub cas: Option<Vec<CryptoAlgorithm>>,
+    // 是否自动校验哈希
+    pub vh: Option<bool>,
+}
+
+// 账本配置
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LedgerSettings {
+    // 共识服务提供者
+    pub consensus_provider: String,
+    // 哈希算法
+    pub hash_algorithm: CryptoAlgorithm,
+    // 支持的密码算法
+    pub crypto_algorithms: Vec<CryptoAlgorithm>,
+    // 是否自动校验哈希
+    pub auto_verify_hash: bool,
+}
+
+impl LedgerSettings {
+    // 是否支持指定编码的密码算法
+    pub fn supports(&self, code: u16) -> bool {
+        self.crypto_algorithms.iter().any(|algorithm| algorithm.code == code)
+    }
+}
+
+// 数据账户 KV，二进制类型数据依次拼接为载荷返回
+#[derive(Serialize, Deserialize)]
+pub struct DataEntryResult {
+    // key
+    pub k: String,
+    // 最新版本
+    pub ver: i64,
+    // value type
+    pub t: ValueType,
+    // value，二进制类型时为空
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub v: Option<String>,
+    // 二进制类型数据在载荷中的长度
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub l: Option<usize>,
+}
+
+// 分页查询数据账户 KV 返回
+#[derive(Serialize, Deserialize)]
+pub struct GetDataEntriesResult {
+    // 响应编码，与 result codes 对应
+    pub rc: ResultCode,
+    // KV 列表
+    pub es: Option<Vec<DataEntryResult>>,
+}
+
+// 查询数据账户 KV 总数返回
+#[derive(Serialize, Deserialize)]
+pub struct GetDataEntriesTotalCountResult {
+    // 响应编码，与 result codes 对应
+    pub rc: ResultCode,
+    // KV 总数
+    pub tc: Option<u64>,
+}
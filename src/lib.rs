@@ -1,6 +1,18 @@
+// 使 #[contract] 生成的 ::jdchain_rust_contract 路径在本 crate 内同样可用
+extern crate self as jdchain_rust_contract;
+
+pub use jdchain_rust_contract_macro::{contract, method, ContractEvent};
+
 // needed mods
 pub mod jdcc_types;
 pub mod jdcc_api;
+pub mod jdcc_ffi;
+pub mod jdcc_mock;
+mod jdcc_overlay;
+pub mod jdcc_storage;
+pub mod jdcc_event;
+pub mod jdcc_auth;
+pub mod jdcc_access;
 
 // user mods
 pub mod sample_contract;
\ No newline at end of file
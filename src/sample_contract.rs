@@ -1,204 +1,231 @@
-use std::ffi::CString;
-use std::os::raw::c_char;
+use crate::contract;
+use crate::jdcc_api::*;
+use crate::jdcc_types::{Address, Algorithm, DataValue, PubKey, ResultCode, ReturnValue, RolesPolicy};
 
-use jdcc_api::*;
-
-use crate::jdcc_api;
-use crate::jdcc_types::SUCCESS;
-
-#[no_mangle]
-// 合约方法前置操作
-// 每次合约方法调用前执行，如无特殊处理逻辑可不暴露此方法
-pub fn before_event() {
-    let service = LedgerService::default();
-    service.logger().info("start call".to_string());
+// 示例合约
+// 导出方法由 #[contract] 生成，参数与返回值的指针处理统一由 jdcc_ffi 完成
+pub struct SampleContract {
+    service: LedgerService,
 }
 
-#[no_mangle]
-// 合约方法后置操作
-// 每次合约方法调用后执行，如无特殊处理逻辑可不暴露此方法
-// 此处运行时仅将合约执行过程中是否出错传递过来，具体错误信息请查阅PEER节点日志
-pub fn post_event(code: i32) {
-    let service = LedgerService::default();
-    match code as u8 {
-        SUCCESS => service.logger().info("end success call".to_string()),
-        _ => service.logger().info("end error call".to_string())
+// KV 读取缓存于本次合约调用内，在 before_event/post_event 中清空
+impl Default for SampleContract {
+    fn default() -> Self {
+        SampleContract { service: LedgerService::invocation() }
     }
 }
 
-// 下列方法为 JD Chain 支持的合约操作
-// 可直接用于合约方法逻辑，对外暴露使用；可组合多个基础方法构建复杂逻辑
-
-#[no_mangle]
-// 获取账本哈希
-pub fn get_ledger_hash() -> *mut c_char {
-    let service = LedgerService::default();
-    let ledger = service.get_ledger_hash();
-    let ledger: String = match ledger {
-        Some(val) => val,
-        _ => "".to_string()
-    };
-    CString::new(ledger).unwrap().into_raw()
-}
+#[contract]
+impl SampleContract {
+    #[method]
+    // 合约方法前置操作
+    // 每次合约方法调用前执行，如无特殊处理逻辑可不暴露此方法
+    pub fn before_event(&self) {
+        self.service.discard();
+        self.service.logger().info("start call".to_string());
+    }
 
-#[no_mangle]
-// 获取合约地址
-pub fn get_contract_address() -> *mut c_char {
-    let service = LedgerService::default();
-    let contract = service.get_contract_address();
-    let contract: String = match contract {
-        Some(val) => val,
-        _ => "".to_string()
-    };
-    CString::new(contract).unwrap().into_raw()
-}
+    #[method]
+    // 合约方法后置操作
+    // 每次合约方法调用后执行，如无特殊处理逻辑可不暴露此方法
+    // 此处运行时仅将合约执行过程中是否出错传递过来，具体错误信息可通过各账本服务接口返回的 ContractError 获取
+    pub fn post_event(&self, code: i32) {
+        match ResultCode::from(code as u8) {
+            ResultCode::Success => self.service.logger().info("end success call".to_string()),
+            _ => self.service.logger().info("end error call".to_string()),
+        }
+        self.service.discard();
+    }
 
-#[no_mangle]
-// 获取合约地址
-pub fn get_tx_hash() -> *mut c_char {
-    let service = LedgerService::default();
-    let tx = service.get_tx_hash();
-    let tx: String = match tx {
-        Some(val) => val,
-        _ => "".to_string()
-    };
-    CString::new(tx).unwrap().into_raw()
-}
+    // 下列方法为 JD Chain 支持的合约操作
+    // 可直接用于合约方法逻辑，对外暴露使用；可组合多个基础方法构建复杂逻辑
 
-#[no_mangle]
-// 获取交易时间
-pub fn get_tx_time() -> u64 {
-    let service = LedgerService::default();
-    let time = service.get_tx_time();
-    match time {
-        Some(val) => val,
-        _ => 0
+    #[method]
+    // 获取账本哈希
+    pub fn get_ledger_hash(&self) -> String {
+        self.service.get_ledger_hash().map(|val| val.to_string()).unwrap_or_default()
     }
-}
 
-#[no_mangle]
-// 获取交易签名用户地址列表
-pub fn get_signers() -> *mut c_char {
-    let service = LedgerService::default();
-    let signers = service.get_signers();
-    let signers: String = match signers {
-        Some(val) => val.join(","),
-        _ => "".to_string()
-    };
-    CString::new(signers).unwrap().into_raw()
-}
+    #[method]
+    // 获取合约地址
+    pub fn get_contract_address(&self) -> String {
+        self.service.get_contract_address().map(|val| val.to_string()).unwrap_or_default()
+    }
 
-#[no_mangle]
-// 注册用户
-pub extern fn register_user(seed_ptr: *mut c_char) -> *mut c_char {
-    let service = LedgerService::default();
-    let address = service.register_user(seed_ptr);
-    let address: String = match address {
-        Some(val) => val,
-        _ => "".to_string()
-    };
-    CString::new(address).unwrap().into_raw()
-}
+    #[method]
+    // 获取交易哈希
+    pub fn get_tx_hash(&self) -> String {
+        self.service.get_tx_hash().map(|val| val.to_string()).unwrap_or_default()
+    }
 
-#[no_mangle]
-// 查询用户
-pub fn get_user(address_ptr: *mut c_char) -> *mut c_char {
-    let service = LedgerService::default();
-    let user = service.get_user(address_ptr);
-    let pub_key: String = match user {
-        Some(val) => val.pubkey,
-        _ => "".to_string()
-    };
-    CString::new(pub_key).unwrap().into_raw()
-}
+    #[method]
+    // 获取交易时间
+    pub fn get_tx_time(&self) -> u64 {
+        self.service.get_tx_time().unwrap_or(0)
+    }
 
-#[no_mangle]
-// 注册数据账户
-pub fn register_data_account(seed_ptr: *mut c_char) -> *mut c_char {
-    let service = LedgerService::default();
-    let address = service.register_data_account(seed_ptr);
-    let address: String = match address {
-        Some(val) => val,
-        _ => "".to_string()
-    };
-    CString::new(address).unwrap().into_raw()
-}
+    #[method]
+    // 获取交易签名用户地址列表
+    pub fn get_signers(&self) -> String {
+        self.service.get_signers().map(|val| val.iter().map(Address::to_string).collect::<Vec<_>>().join(",")).unwrap_or_default()
+    }
 
-#[no_mangle]
-// 查询数据账户
-pub fn get_data_account(address_ptr: *mut c_char) -> *mut c_char {
-    let service = LedgerService::default();
-    let da = service.get_data_account(address_ptr);
-    let pub_key: String = match da {
-        Some(val) => val.pubkey,
-        _ => "".to_string()
-    };
-    CString::new(pub_key).unwrap().into_raw()
-}
+    #[method]
+    // 获取最新区块高度
+    pub fn get_latest_block_height(&self) -> i64 {
+        self.service.get_latest_block_height().unwrap_or(-1)
+    }
 
-#[no_mangle]
-// 写KV，字符类型，不带版本
-pub fn set_text(addr_ptr: *mut c_char, key_ptr: *mut c_char, value_ptr: *mut c_char) -> i64 {
-    let service = LedgerService::default();
-    let ver = service.set_text(addr_ptr, key_ptr, value_ptr);
-    match ver {
-        Some(val) => val,
-        _ => -1
+    #[method]
+    // 获取最新区块哈希
+    pub fn get_latest_block_hash(&self) -> String {
+        self.service.get_latest_block_hash().map(|val| val.to_string()).unwrap_or_default()
     }
-}
 
-#[no_mangle]
-// 写KV，字符类型
-pub fn set_text_with_version(addr_ptr: *mut c_char, key_ptr: *mut c_char, value_ptr: *mut c_char, version: i64) -> i64 {
-    let service = LedgerService::default();
-    let ver = service.set_text_with_version(addr_ptr, key_ptr, value_ptr, version);
-    match ver {
-        Some(val) => val,
-        _ => -1
+    #[method]
+    // 获取参与共识的节点地址列表
+    pub fn get_consensus_nodes(&self) -> String {
+        self.service.get_consensus_nodes().map(|val| val.iter().map(|node| node.address.to_string()).collect::<Vec<_>>().join(",")).unwrap_or_default()
     }
-}
 
-#[no_mangle]
-// 写KV，数值类型，不带版本
-pub fn set_int64(addr_ptr: *mut c_char, key_ptr: *mut c_char, value_ptr: i64) -> i64 {
-    let service = LedgerService::default();
-    let ver = service.set_int64(addr_ptr, key_ptr, value_ptr);
-    match ver {
-        Some(val) => val,
-        _ => -1
+    #[method]
+    // 校验地址是否为交易签名用户
+    pub fn is_signer(&self, address: Address) -> bool {
+        self.service.auth().is_signer(&address).unwrap_or(false)
     }
-}
 
-#[no_mangle]
-// 写KV，数值类型
-pub fn set_int64_with_version(addr_ptr: *mut c_char, key_ptr: *mut c_char, value_ptr: i64, version: i64) -> i64 {
-    let service = LedgerService::default();
-    let ver = service.set_int64_with_version(addr_ptr, key_ptr, value_ptr, version);
-    match ver {
-        Some(val) => val,
-        _ => -1
+    #[method]
+    // 注册用户
+    pub fn register_user(&self, seed: String) -> String {
+        self.service.register_user(&seed).map(|val| val.to_string()).unwrap_or_default()
     }
-}
 
-#[no_mangle]
-// 查询数据版本
-pub fn get_value_version(addr_ptr: *mut c_char, key_ptr: *mut c_char) -> i64 {
-    let service = LedgerService::default();
-    let ver = service.get_value_version(addr_ptr, key_ptr);
-    match ver {
-        Some(val) => val,
-        _ => -1
+    #[method]
+    // 注册用户，使用指定签名算法：ED25519、SM2、ECDSA、RSA
+    pub fn register_user_with(&self, seed: String, algorithm: Algorithm) -> String {
+        self.service.register_user_with(&seed, algorithm).map(|val| val.to_string()).unwrap_or_default()
+    }
+
+    #[method]
+    // 以公钥注册用户，私钥无需上链
+    pub fn register_user_with_pubkey(&self, pubkey: PubKey) -> String {
+        self.service.register_user_with_pubkey(&pubkey).map(|val| val.to_string()).unwrap_or_default()
+    }
+
+    #[method]
+    // 查询用户
+    pub fn get_user(&self, address: Address) -> String {
+        self.service.get_user(&address).ok().flatten().map(|val| val.pubkey.to_string()).unwrap_or_default()
+    }
+
+    #[method]
+    // 注册数据账户
+    pub fn register_data_account(&self, seed: String) -> String {
+        self.service.register_data_account(&seed).map(|val| val.to_string()).unwrap_or_default()
+    }
+
+    #[method]
+    // 注册数据账户，使用指定签名算法：ED25519、SM2、ECDSA、RSA
+    pub fn register_data_account_with(&self, seed: String, algorithm: Algorithm) -> String {
+        self.service.register_data_account_with(&seed, algorithm).map(|val| val.to_string()).unwrap_or_default()
     }
-}
 
-#[no_mangle]
-// 查询数据
-pub fn get_value(addr_ptr: *mut c_char, key_ptr: *mut c_char, version: i64) -> *mut c_char {
-    let service = LedgerService::default();
-    let kv = service.get_value(addr_ptr, key_ptr, version);
-    let value: String = match kv {
-        Some(val) => val.value,
-        _ => "".to_string()
-    };
-    CString::new(value).unwrap().into_raw()
-}
\ No newline at end of file
+    #[method]
+    // 以公钥注册数据账户，私钥无需上链
+    pub fn register_data_account_with_pubkey(&self, pubkey: PubKey) -> String {
+        self.service.register_data_account_with_pubkey(&pubkey).map(|val| val.to_string()).unwrap_or_default()
+    }
+
+    #[method]
+    // 查询数据账户
+    pub fn get_data_account(&self, address: Address) -> String {
+        self.service.get_data_account(&address).ok().flatten().map(|val| val.pubkey.to_string()).unwrap_or_default()
+    }
+
+    #[method]
+    // 写KV，字符类型，不带版本
+    pub fn set_text(&self, address: Address, key: String, value: String) -> i64 {
+        self.service.set_text(&address, &key, &value).unwrap_or(-1)
+    }
+
+    #[method]
+    // 写KV，字符类型
+    pub fn set_text_with_version(&self, address: Address, key: String, value: String, version: i64) -> i64 {
+        self.service.set_text_with_version(&address, &key, &value, version).unwrap_or(-1)
+    }
+
+    #[method]
+    // 写KV，数值类型，不带版本
+    pub fn set_int64(&self, address: Address, key: String, value: i64) -> i64 {
+        self.service.set_int64(&address, &key, value).unwrap_or(-1)
+    }
+
+    #[method]
+    // 写KV，数值类型
+    pub fn set_int64_with_version(&self, address: Address, key: String, value: i64, version: i64) -> i64 {
+        self.service.set_int64_with_version(&address, &key, value, version).unwrap_or(-1)
+    }
+
+    #[method]
+    // 查询数据版本
+    pub fn get_value_version(&self, address: Address, key: String) -> i64 {
+        self.service.get_value_version(&address, &key).unwrap_or(-1)
+    }
+
+    #[method]
+    // 查询数据
+    pub fn get_value(&self, address: Address, key: String, version: i64) -> String {
+        self.service.get_value(&address, &key, version).ok().flatten().map(|val| val.value.to_string()).unwrap_or_default()
+    }
+
+    #[method]
+    // 查询数据账户中的 key 总数
+    pub fn get_data_entries_total_count(&self, address: Address) -> i64 {
+        self.service.get_data_entries_total_count(&address).map(|val| val as i64).unwrap_or(-1)
+    }
+
+    #[method]
+    // 分页查询数据账户中的 key，以逗号分隔
+    pub fn get_data_keys(&self, address: Address, from_index: i64, count: i64) -> String {
+        self.service
+            .get_data_entries(&address, from_index.max(0) as u64, count.max(0) as u64)
+            .map(|val| val.iter().map(|entry| entry.key.as_str()).collect::<Vec<_>>().join(","))
+            .unwrap_or_default()
+    }
+
+    #[method]
+    // 注册事件账户
+    pub fn register_event_account(&self, seed: String) -> String {
+        self.service.register_event_account(&seed).map(|val| val.to_string()).unwrap_or_default()
+    }
+
+    #[method]
+    // 发布事件，字符类型内容
+    pub fn publish_event(&self, account: Address, topic: String, content: String, sequence: i64) -> i64 {
+        self.service.publish_event(&account, &topic, DataValue::Text(content), sequence).unwrap_or(-1)
+    }
+
+    #[method]
+    // 查询主题下的最新事件内容
+    pub fn get_latest_event(&self, account: Address, topic: String) -> String {
+        self.service.get_latest_event(&account, &topic).ok().flatten().map(|val| val.content.to_string()).unwrap_or_default()
+    }
+
+    #[method]
+    // 调用合约方法，字符类型参数
+    pub fn invoke_contract(&self, address: Address, method: String, arg: String) -> String {
+        self.service.invoke_contract(&address, &method, &[DataValue::Text(arg)]).ok().and_then(ReturnValue::into_value).map(|val| val.to_string()).unwrap_or_default()
+    }
+
+    #[method]
+    // 查询用户角色，以逗号分隔
+    pub fn get_user_roles(&self, address: Address) -> String {
+        self.service.get_user_roles(&address).map(|val| val.roles.join(",")).unwrap_or_default()
+    }
+
+    #[method]
+    // 授予用户角色，合并策略为并集
+    pub fn authorize_user(&self, address: Address, role: String) -> bool {
+        self.service.authorize_user(&address, &[&role], RolesPolicy::Union).is_ok()
+    }
+}
@@ -0,0 +1,99 @@
+// 基于 MockLedger 校验所有者、角色访问控制及变更事件
+
+mod common;
+
+use jdchain_rust_contract::jdcc_access::{OwnershipTransferred, RoleGranted, RoleRevoked};
+use jdchain_rust_contract::jdcc_api::LedgerService;
+use jdchain_rust_contract::jdcc_event::ContractEvent;
+use jdchain_rust_contract::jdcc_mock::MockLedger;
+use jdchain_rust_contract::jdcc_types::{Address, ContractError};
+
+use common::{account, user};
+
+fn ledger(signers: &[Address]) -> MockLedger {
+    common::ledger()
+        .with_event_account(&user("events"))
+        .with_signers(signers)
+}
+
+#[test]
+fn owner_is_initialized_once_and_transferable() {
+    let ledger = ledger(&[user("alice")]);
+    let service = LedgerService::new(&ledger);
+    let access = service.access(&account()).with_events(&user("events"));
+
+    match access.only_owner() {
+        Err(ContractError::OwnerNotSet) => {}
+        other => panic!("unexpected result {:?}", other),
+    }
+    access.init_owner(&user("alice")).unwrap();
+    match access.init_owner(&user("bob")) {
+        Err(ContractError::OwnerAlreadySet(owner)) => assert_eq!(owner, user("alice")),
+        other => panic!("unexpected result {:?}", other),
+    }
+    assert_eq!(access.only_owner().unwrap(), user("alice"));
+
+    access.transfer_ownership(&user("bob")).unwrap();
+    assert_eq!(access.owner().unwrap(), Some(user("bob")));
+    match access.transfer_ownership(&user("alice")) {
+        Err(ContractError::MissingSigner(owner)) => assert_eq!(owner, user("bob")),
+        other => panic!("unexpected result {:?}", other),
+    }
+    let event = service.get_latest_contract_event::<OwnershipTransferred>(&user("events")).unwrap().unwrap();
+    assert_eq!(event, OwnershipTransferred { previous: Some(user("alice")), owner: user("bob") });
+}
+
+#[test]
+fn owner_must_sign_initialization() {
+    let ledger = ledger(&[user("alice")]);
+    let service = LedgerService::new(&ledger);
+    let access = service.access(&account());
+
+    match access.init_owner(&user("mallory")) {
+        Err(ContractError::MissingSigner(owner)) => assert_eq!(owner, user("mallory")),
+        other => panic!("unexpected result {:?}", other),
+    }
+    assert_eq!(access.owner().unwrap(), None);
+    access.init_owner(&user("alice")).unwrap();
+}
+
+#[test]
+fn roles_are_granted_by_owner_and_checked_against_signers() {
+    let ledger = ledger(&[user("alice"), user("carol")]);
+    let service = LedgerService::new(&ledger);
+    let access = service.access(&account()).with_events(&user("events"));
+    access.init_owner(&user("alice")).unwrap();
+
+    match access.only_role("minter") {
+        Err(ContractError::MissingRole(role)) => assert_eq!(role, "minter"),
+        other => panic!("unexpected result {:?}", other),
+    }
+    assert!(access.grant_role("minter", &user("carol")).unwrap());
+    assert!(!access.grant_role("minter", &user("carol")).unwrap());
+    assert!(access.grant_role("minter", &user("dave")).unwrap());
+    assert_eq!(access.role_size("minter").unwrap(), 2);
+    assert_eq!(access.only_role("minter").unwrap(), user("carol"));
+    assert!(!access.has_role("pauser", &user("carol")).unwrap());
+
+    assert!(access.revoke_role("minter", &user("carol")).unwrap());
+    assert!(!access.revoke_role("minter", &user("carol")).unwrap());
+    assert!(access.only_role("minter").is_err());
+
+    let granted = service.get_latest_contract_event::<RoleGranted>(&user("events")).unwrap().unwrap();
+    assert_eq!(granted, RoleGranted { role: "minter".to_string(), account: user("dave") });
+    let revoked = service.get_latest_event(&user("events"), "access.role_revoked").unwrap().unwrap();
+    assert_eq!(revoked.sequence, 0);
+    assert_eq!(RoleRevoked::from_event(&revoked).unwrap(), RoleRevoked { role: "minter".to_string(), account: user("carol") });
+}
+
+#[test]
+fn role_changes_require_owner_signature() {
+    let owner_ledger = ledger(&[user("alice")]);
+    LedgerService::new(&owner_ledger).access(&account()).init_owner(&user("alice")).unwrap();
+    let ledger = owner_ledger.with_signers(&[user("bob")]);
+    let service = LedgerService::new(&ledger);
+    let access = service.access(&account());
+
+    assert!(access.grant_role("minter", &user("bob")).is_err());
+    assert!(!access.has_role("minter", &user("bob")).unwrap());
+}
@@ -0,0 +1,62 @@
+// 基于 MockLedger 校验签名授权及签名用户列表缓存
+
+mod common;
+
+use jdchain_rust_contract::jdcc_api::LedgerService;
+use jdchain_rust_contract::jdcc_mock::MockLedger;
+use jdchain_rust_contract::jdcc_types::ContractError;
+
+use common::{user, CountingHost};
+
+fn ledger() -> MockLedger {
+    MockLedger::new().with_signers(&[user("alice"), user("bob"), user("carol")])
+}
+
+#[test]
+fn require_signer_and_any_signer() {
+    let ledger = ledger();
+    let service = LedgerService::new(&ledger);
+    let auth = service.auth();
+
+    auth.require_signer(&user("alice")).unwrap();
+    match auth.require_signer(&user("dave")) {
+        Err(ContractError::MissingSigner(address)) => assert_eq!(address, user("dave")),
+        other => panic!("unexpected result {:?}", other),
+    }
+    assert_eq!(auth.require_any_signer(&[user("dave"), user("bob"), user("carol")]).unwrap(), user("bob"));
+    match auth.require_any_signer(&[user("dave")]) {
+        Err(ContractError::InsufficientSigners { required: 1, signed: 0 }) => {}
+        other => panic!("unexpected result {:?}", other),
+    }
+}
+
+#[test]
+fn require_threshold_counts_distinct_signers() {
+    let ledger = ledger();
+    let service = LedgerService::new(&ledger);
+    let auth = service.auth();
+    let committee = [user("alice"), user("alice"), user("dave"), user("carol")];
+
+    assert_eq!(auth.require_threshold(&committee, 2).unwrap(), vec![user("alice"), user("carol")]);
+    match auth.require_threshold(&committee, 3) {
+        Err(ContractError::InsufficientSigners { required: 3, signed: 2 }) => {}
+        other => panic!("unexpected result {:?}", other),
+    }
+    assert!(auth.require_threshold(&[], 0).unwrap().is_empty());
+}
+
+#[test]
+fn signers_are_queried_once_per_invocation() {
+    let ledger = ledger();
+    let host = CountingHost::new(&ledger);
+    let service = LedgerService::new(&host).with_overlay();
+
+    service.auth().require_signer(&user("alice")).unwrap();
+    service.auth().require_any_signer(&[user("bob")]).unwrap();
+    assert_eq!(service.get_signers().unwrap().len(), 3);
+    assert_eq!(host.take(), 1);
+
+    service.discard();
+    service.auth().require_signer(&user("carol")).unwrap();
+    assert_eq!(host.take(), 1);
+}
@@ -0,0 +1,69 @@
+// 注入自定义 HostBackend，校验账本服务发出的请求及宿主错误的传递
+
+use std::cell::RefCell;
+
+use jdchain_rust_contract::jdcc_api::{HostBackend, LedgerService};
+use jdchain_rust_contract::jdcc_types::ContractError;
+
+// 记录请求的宿主，call 依次返回预设的返回数据，预设数据用尽时返回错误
+#[derive(Default)]
+struct RecordingHost {
+    calls: RefCell<Vec<String>>,
+    sends: RefCell<Vec<String>>,
+    responses: RefCell<Vec<Vec<u8>>>,
+}
+
+impl RecordingHost {
+    fn respond(self, response: &str) -> Self {
+        self.responses.borrow_mut().push(response.as_bytes().to_vec());
+        self
+    }
+}
+
+impl HostBackend for RecordingHost {
+    fn call(&self, req: &[u8]) -> Result<Vec<u8>, ContractError> {
+        self.calls.borrow_mut().push(String::from_utf8(req.to_vec()).unwrap());
+        let mut responses = self.responses.borrow_mut();
+        if responses.is_empty() {
+            return Err(ContractError::Decode("host offline".to_string()));
+        }
+        Ok(responses.remove(0))
+    }
+
+    fn send(&self, req: &[u8]) {
+        self.sends.borrow_mut().push(String::from_utf8(req.to_vec()).unwrap());
+    }
+}
+
+#[test]
+fn queries_are_sent_through_call() {
+    let host = RecordingHost::default().respond(r#"{"rc":0,"tt":7}"#).respond(r#"{"rc":0,"h":3}"#);
+    let service = LedgerService::new(&host);
+
+    assert_eq!(service.get_tx_time().unwrap(), 7);
+    assert_eq!(service.get_latest_block_height().unwrap(), 3);
+    assert_eq!(*host.calls.borrow(), vec![r#"{"rt":6}"#, r#"{"rt":33}"#]);
+    assert!(host.sends.borrow().is_empty());
+    assert!(std::ptr::eq(*service.backend(), &host));
+}
+
+#[test]
+fn logs_are_sent_without_reading_response() {
+    let host = RecordingHost::default();
+    let service = LedgerService::new(&host);
+
+    service.logger().info("hello".to_string());
+    service.logger().error("oops".to_string());
+    assert!(host.calls.borrow().is_empty());
+    assert_eq!(*host.sends.borrow(), vec![r#"{"rt":0,"l":2,"m":"hello"}"#, r#"{"rt":0,"l":3,"m":"oops"}"#]);
+}
+
+#[test]
+fn backend_errors_are_returned_unchanged() {
+    let service = LedgerService::new(RecordingHost::default());
+    match service.get_tx_hash() {
+        Err(ContractError::Decode(msg)) => assert_eq!(msg, "host offline"),
+        other => panic!("unexpected result {:?}", other),
+    }
+    assert_eq!(service.backend().calls.borrow().len(), 1);
+}
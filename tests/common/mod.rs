@@ -0,0 +1,109 @@
+// 集成测试共用的测试账户及宿主
+
+#![allow(dead_code)]
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+
+use jdchain_rust_contract::jdcc_api::HostBackend;
+use jdchain_rust_contract::jdcc_mock::MockLedger;
+use jdchain_rust_contract::jdcc_types::{Address, ContractError, HashDigest, PubKey};
+
+// JD Chain 公钥及其对应地址
+pub const PUBKEY: &str = "7VeRG8jpBNg15W7HCrFyLG7TdpUea5jnHAUDbmxAkK6ZYqu4";
+pub const ADDRESS: &str = "LdeNgGn7tPYXNi4vAhXN57qAYtb57NvAUDvvg";
+
+// 统计 call 次数的宿主
+pub struct CountingHost<'a> {
+    ledger: &'a MockLedger,
+    calls: Cell<usize>,
+}
+
+impl<'a> CountingHost<'a> {
+    pub fn new(ledger: &'a MockLedger) -> Self {
+        CountingHost { ledger, calls: Cell::new(0) }
+    }
+
+    // 返回并清零调用次数
+    pub fn take(&self) -> usize {
+        self.calls.replace(0)
+    }
+}
+
+impl HostBackend for CountingHost<'_> {
+    fn call(&self, req: &[u8]) -> Result<Vec<u8>, ContractError> {
+        self.calls.set(self.calls.get() + 1);
+        self.ledger.call(req)
+    }
+
+    fn send(&self, req: &[u8]) {
+        self.ledger.send(req)
+    }
+}
+
+// 返回固定数据的宿主
+pub struct FixedHost(pub Vec<u8>);
+
+impl HostBackend for FixedHost {
+    fn call(&self, _: &[u8]) -> Result<Vec<u8>, ContractError> {
+        Ok(self.0.clone())
+    }
+
+    fn send(&self, _: &[u8]) {}
+}
+
+// 按线程统计未释放的内存分配次数，测试文件通过 #[global_allocator] 启用
+pub struct CountingAlloc;
+
+thread_local! {
+    static LIVE: Cell<isize> = const { Cell::new(0) };
+}
+
+fn track(delta: isize) {
+    let _ = LIVE.try_with(|live| live.set(live.get() + delta));
+}
+
+unsafe impl GlobalAlloc for CountingAlloc {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        track(1);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        track(-1);
+        System.dealloc(ptr, layout)
+    }
+}
+
+pub fn live_allocations() -> isize {
+    LIVE.with(Cell::get)
+}
+
+pub fn pubkey() -> PubKey {
+    PUBKEY.parse().unwrap()
+}
+
+// 以 PUBKEY 注册的数据账户地址
+pub fn account() -> Address {
+    ADDRESS.parse().unwrap()
+}
+
+// 预置数据账户 account() 的账本
+pub fn ledger() -> MockLedger {
+    MockLedger::new().with_data_account(&account(), &pubkey())
+}
+
+// 按 seed 生成的 ED25519 地址
+fn seed_address(seed: &str) -> Address {
+    PubKey::new(0x4115, HashDigest::sha256(seed.as_bytes()).digest()).address()
+}
+
+// 按 seed 生成的用户地址
+pub fn user(seed: &str) -> Address {
+    seed_address(seed)
+}
+
+// 按 seed 生成的合约地址
+pub fn contract(seed: &str) -> Address {
+    seed_address(seed)
+}
@@ -0,0 +1,97 @@
+// 直接调用 #[contract] 生成的导出方法，校验参数解码、返回值编码及返回字符串的释放，以及空指针、非 UTF-8 参数与含 NUL 返回值的拒绝
+
+mod common;
+
+use std::ffi::{CStr, CString};
+use std::os::raw::c_char;
+
+use jdchain_rust_contract::contract;
+use jdchain_rust_contract::jdcc_ffi::{drop_string, str_from_ptr, string_into_ptr, FromAbi};
+use jdchain_rust_contract::jdcc_types::{Address, ContractError};
+
+use common::{live_allocations, CountingAlloc, ADDRESS};
+
+#[global_allocator]
+static ALLOC: CountingAlloc = CountingAlloc;
+
+#[derive(Default)]
+struct Greeter {
+    calls: i64,
+}
+
+#[contract]
+impl Greeter {
+    #[method]
+    pub fn greeter_describe(&mut self, name: String, owner: Address, amount: i64) -> String {
+        self.calls += 1;
+        format!("{}:{}:{}:{}", name, owner, amount, self.calls)
+    }
+
+    #[method]
+    pub fn greeter_owner(owner: Address) -> Result<Address, ContractError> {
+        Ok(owner)
+    }
+
+    #[method]
+    pub fn greeter_double(amount: i64) -> i64 {
+        amount * 2
+    }
+}
+
+// 读取导出方法返回的字符串后交由 drop_string 释放
+unsafe fn take_string(ptr: *mut c_char) -> String {
+    assert!(!ptr.is_null());
+    let value = CStr::from_ptr(ptr).to_str().unwrap().to_string();
+    drop_string(ptr);
+    value
+}
+
+#[test]
+fn exported_method_decodes_c_string_arguments() {
+    let name = CString::new("jd 链").unwrap();
+    let owner = CString::new(ADDRESS).unwrap();
+    let ret = unsafe { greeter_describe(name.as_ptr() as *mut _, owner.as_ptr() as *mut _, -7) };
+    // 每次调用构建新的合约实例
+    assert_eq!(unsafe { take_string(ret) }, format!("jd 链:{}:-7:1", ADDRESS));
+    let ret = unsafe { greeter_describe(name.as_ptr() as *mut _, owner.as_ptr() as *mut _, i64::MAX) };
+    assert_eq!(unsafe { take_string(ret) }, format!("jd 链:{}:{}:1", ADDRESS, i64::MAX));
+
+    // 参数仍归调用方所有
+    assert_eq!(name.to_str().unwrap(), "jd 链");
+    assert_eq!(unsafe { greeter_double(21) }, 42);
+}
+
+#[test]
+fn returned_string_is_released_by_drop_string() {
+    let owner = CString::new(ADDRESS).unwrap();
+    let before = live_allocations();
+    let ret = unsafe { greeter_owner(owner.as_ptr() as *mut _) };
+    assert_eq!(live_allocations(), before + 1);
+    assert_eq!(unsafe { take_string(ret) }, ADDRESS);
+    assert_eq!(live_allocations(), before);
+
+    // 空指针无需释放
+    unsafe { drop_string(std::ptr::null_mut()) };
+}
+
+#[test]
+fn invalid_c_string_arguments_are_rejected() {
+    let null = std::ptr::null_mut::<c_char>();
+    assert!(matches!(unsafe { str_from_ptr(null) }, Err(ContractError::NullPointer)));
+    assert!(matches!(unsafe { String::from_abi(null) }, Err(ContractError::NullPointer)));
+    assert!(matches!(unsafe { Address::from_abi(null) }, Err(ContractError::NullPointer)));
+
+    let invalid = CString::new(vec![b'j', 0xff, 0xfe]).unwrap();
+    assert!(matches!(unsafe { str_from_ptr(invalid.as_ptr()) }, Err(ContractError::InvalidUtf8)));
+    assert!(matches!(unsafe { String::from_abi(invalid.as_ptr() as *mut _) }, Err(ContractError::InvalidUtf8)));
+}
+
+#[test]
+fn returned_string_with_interior_nul_is_rejected() {
+    assert!(matches!(string_into_ptr("jd\0chain".to_string()), Err(ContractError::Encode(_))));
+
+    let before = live_allocations();
+    let ptr = string_into_ptr("jd chain".to_string()).unwrap();
+    assert_eq!(unsafe { take_string(ptr) }, "jd chain");
+    assert_eq!(live_allocations(), before);
+}
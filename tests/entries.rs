@@ -0,0 +1,88 @@
+// 基于 MockLedger 校验数据账户 KV 分页查询及分页迭代
+
+mod common;
+
+use jdchain_rust_contract::jdcc_api::LedgerService;
+use jdchain_rust_contract::jdcc_mock::MockLedger;
+use jdchain_rust_contract::jdcc_types::{ContractError, DataValue, ErrorCode, Message};
+
+use common::{account, CountingHost, FixedHost};
+
+// 依次写入 key0..key4，key1 为字节类型，key0 写入两次
+fn ledger() -> MockLedger {
+    let ledger = common::ledger();
+    let service = LedgerService::new(&ledger);
+    let account = account();
+    service.set_text(&account, "key0", "a").unwrap();
+    service.set_bytes(&account, "key1", &[1u8, 2, 3]).unwrap();
+    service.set_int64(&account, "key2", 2).unwrap();
+    service.set_text(&account, "key0", "b").unwrap();
+    service.set_image(&account, "key3", &[4u8; 5]).unwrap();
+    service.set_text(&account, "key4", "e").unwrap();
+    ledger
+}
+
+#[test]
+fn entries_are_listed_in_first_write_order() {
+    let ledger = ledger();
+    let account = account();
+    let service = LedgerService::new(&ledger);
+
+    assert_eq!(service.get_data_entries_total_count(&account).unwrap(), 5);
+    let entries = service.get_data_entries(&account, 0, 4).unwrap();
+    let keys: Vec<&str> = entries.iter().map(|entry| entry.key.as_str()).collect();
+    assert_eq!(keys, vec!["key0", "key1", "key2", "key3"]);
+    assert_eq!((entries[0].value.clone(), entries[0].version), (DataValue::text("b"), 1));
+    assert_eq!(entries[1].value, DataValue::bytes(vec![1u8, 2, 3]));
+    assert_eq!(entries[3].value, DataValue::image(vec![4u8; 5]));
+
+    assert_eq!(service.get_data_entries(&account, 4, 10).unwrap().len(), 1);
+    assert!(service.get_data_entries(&account, 5, 10).unwrap().is_empty());
+}
+
+#[test]
+fn iterator_pages_lazily() {
+    let ledger = ledger();
+    let account = account();
+    let host = CountingHost::new(&ledger);
+    let service = LedgerService::new(&host);
+
+    let mut entries = service.data_entries(&account, 2);
+    assert_eq!(host.take(), 0);
+    assert_eq!(entries.next().unwrap().unwrap().key, "key0");
+    assert_eq!(host.take(), 1);
+    let rest: Vec<String> = entries.map(|entry| entry.unwrap().key).collect();
+    assert_eq!(rest, vec!["key1", "key2", "key3", "key4"]);
+    assert_eq!(host.take(), 2);
+
+    // 总数为页大小整数倍时以一次空页查询结束
+    assert_eq!(service.data_entries(&account, 5).count(), 5);
+    assert_eq!(host.take(), 2);
+}
+
+#[test]
+fn iterator_stops_after_error() {
+    let ledger = MockLedger::new();
+    let service = LedgerService::new(&ledger);
+    let mut entries = service.data_entries(&account(), 10);
+
+    match entries.next() {
+        Some(Err(e)) => assert_eq!(e.sub_code(), Some(ErrorCode::AccountNotFound)),
+        other => panic!("unexpected result {:?}", other.map(|entry| entry.map(|entry| entry.key))),
+    }
+    assert!(entries.next().is_none());
+}
+
+#[test]
+fn oversized_payload_lengths_are_rejected() {
+    let header = format!(r#"{{"rc":0,"es":[{{"k":"a","ver":0,"t":"BYTES","l":1}},{{"k":"b","ver":0,"t":"BYTES","l":{}}}]}}"#, usize::MAX);
+    let host = FixedHost(Message::with_payload(header, vec![1u8, 2]).encode());
+    match LedgerService::new(&host).get_data_entries(&account(), 0, 2) {
+        Err(ContractError::Decode(_)) => {}
+        other => panic!("unexpected result {:?}", other.map(|entries| entries.len())),
+    }
+
+    let header = r#"{"rc":0,"es":[{"k":"a","ver":0,"t":"BYTES","l":3}]}"#.to_string();
+    let host = FixedHost(Message::with_payload(header, vec![1u8, 2]).encode());
+    assert!(matches!(LedgerService::new(&host).get_data_entries(&account(), 0, 1), Err(ContractError::Decode(_))));
+}
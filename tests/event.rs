@@ -0,0 +1,68 @@
+// 基于 MockLedger 校验类型化事件的发布与解码
+
+mod common;
+
+use serde::{Deserialize, Serialize};
+
+use jdchain_rust_contract::jdcc_api::LedgerService;
+use jdchain_rust_contract::jdcc_event::ContractEvent;
+use jdchain_rust_contract::jdcc_mock::MockLedger;
+use jdchain_rust_contract::jdcc_types::{ContractError, DataValue};
+
+use common::account;
+
+#[derive(ContractEvent, Serialize, Deserialize, Debug, PartialEq)]
+#[event(topic = "order.created", version = 2)]
+struct OrderCreated {
+    id: u64,
+    amount: i64,
+    #[serde(default)]
+    memo: String,
+}
+
+#[derive(ContractEvent, Serialize, Deserialize, Debug, PartialEq)]
+struct Paused {}
+
+fn ledger() -> MockLedger {
+    MockLedger::new().with_event_account(&account())
+}
+
+#[test]
+fn derive_uses_declared_or_default_topic_and_version() {
+    assert_eq!((OrderCreated::TOPIC, OrderCreated::VERSION), ("order.created", 2));
+    assert_eq!((Paused::TOPIC, Paused::VERSION), ("Paused", 1));
+}
+
+#[test]
+fn emitted_events_decode_with_shared_definition() {
+    let ledger = ledger();
+    let account = account();
+    let service = LedgerService::new(&ledger);
+
+    assert_eq!(service.get_latest_contract_event::<OrderCreated>(&account).unwrap(), None);
+    let first = OrderCreated { id: 1, amount: 10, memo: String::new() };
+    assert_eq!(service.emit_event(&account, &first).unwrap(), 0);
+    let second = OrderCreated { id: 2, amount: 20, memo: "gift".to_string() };
+    assert_eq!(service.emit_event(&account, &second).unwrap(), 1);
+
+    let event = service.get_latest_event(&account, "order.created").unwrap().unwrap();
+    assert_eq!(event.sequence, 1);
+    assert_eq!(OrderCreated::from_event(&event).unwrap(), second);
+    assert_eq!(service.get_latest_contract_event::<OrderCreated>(&account).unwrap(), Some(second));
+    match Paused::from_event(&event) {
+        Err(ContractError::Decode(_)) => {}
+        other => panic!("unexpected result {:?}", other),
+    }
+}
+
+#[test]
+fn decode_accepts_older_and_rejects_newer_versions() {
+    let older = DataValue::Json(r#"{"ver":1,"data":{"id":3,"amount":30}}"#.to_string());
+    assert_eq!(OrderCreated::decode(&older).unwrap(), OrderCreated { id: 3, amount: 30, memo: String::new() });
+
+    let newer = DataValue::Json(r#"{"ver":3,"data":{"id":3,"amount":30,"memo":""}}"#.to_string());
+    match OrderCreated::decode(&newer) {
+        Err(ContractError::Decode(_)) => {}
+        other => panic!("unexpected result {:?}", other),
+    }
+}
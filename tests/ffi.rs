@@ -0,0 +1,139 @@
+// 通过模拟 sys_call/sys_msg 导入函数在本地驱动 WasmHost，校验请求与返回缓冲区的内存管理及宿主返回指针的校验
+
+mod common;
+
+use std::cell::{Cell, RefCell};
+use std::os::raw::c_char;
+use std::{ptr, slice};
+
+use jdchain_rust_contract::jdcc_api::{HostBackend, LedgerService};
+use jdchain_rust_contract::jdcc_ffi::WasmHost;
+use jdchain_rust_contract::jdcc_mock::MockLedger;
+use jdchain_rust_contract::jdcc_types::ContractError;
+
+use common::{live_allocations, CountingAlloc};
+
+#[global_allocator]
+static ALLOC: CountingAlloc = CountingAlloc;
+
+const LEDGER_HASH: &str = "j5iSgS2dQbtf2zNTPFLnWVJCJ1YAbUETPrqgecVoaucBnG";
+
+thread_local! {
+    static LEDGER: MockLedger = common::ledger().with_ledger_hash(&LEDGER_HASH.parse().unwrap());
+    // 宿主侧待读取的返回数据
+    static PENDING: RefCell<Vec<u8>> = const { RefCell::new(Vec::new()) };
+    // 宿主最近一次写入的合约缓冲区
+    static WRITTEN: Cell<usize> = const { Cell::new(0) };
+}
+
+// 模拟宿主：处理请求并暂存返回数据，返回数据长度，不含结尾 NUL
+unsafe extern "C" fn mock_sys_call(req_len: i32, req_ptr: *const c_char) -> usize {
+    let req = slice::from_raw_parts(req_ptr as *const u8, req_len as usize);
+    let msg = LEDGER.with(|ledger| ledger.handle(req));
+    let msg_len = msg.len();
+    PENDING.with(|pending| *pending.borrow_mut() = msg);
+    msg_len
+}
+
+// 模拟宿主：将暂存的返回数据按长度写入合约分配的缓冲区
+unsafe extern "C" fn mock_sys_msg(msg_len: i32, msg_ptr: *mut c_char) -> *mut c_char {
+    let msg = PENDING.with(|pending| pending.replace(Vec::new()));
+    assert_eq!(msg.len(), msg_len as usize);
+    ptr::copy_nonoverlapping(msg.as_ptr(), msg_ptr as *mut u8, msg.len());
+    WRITTEN.with(|written| written.set(msg_ptr as usize));
+    msg_ptr
+}
+
+fn mock_host() -> WasmHost {
+    WasmHost::with_imports(mock_sys_call, mock_sys_msg)
+}
+
+#[test]
+fn sys_call_does_not_leak_request_buffers() {
+    let service = LedgerService::new(mock_host());
+    // 预热线程局部状态，且以日志请求结束，使宿主侧暂存一条返回数据
+    assert_eq!(service.get_ledger_hash().unwrap().as_str(), LEDGER_HASH);
+    let missing = common::user("missing");
+    service.logger().info("warm up".to_string());
+    LEDGER.with(MockLedger::take_logs);
+
+    let before = live_allocations();
+    for i in 0..100 {
+        assert_eq!(service.get_ledger_hash().unwrap().as_str(), LEDGER_HASH);
+        assert!(service.get_value_version(&missing, "k").is_err());
+        service.logger().info(format!("call {}", i));
+        assert_eq!(LEDGER.with(MockLedger::take_logs), vec![format!("[INFO] call {}", i)]);
+    }
+    assert_eq!(live_allocations(), before);
+}
+
+#[test]
+fn response_is_owned_buffer_written_by_host() {
+    let host = mock_host();
+    let req = br#"{"rt":3}"#;
+    let expected = format!(r#"{{"rc":0,"lh":"{}"}}"#, LEDGER_HASH);
+    assert_eq!(host.call(req).unwrap(), expected.as_bytes());
+
+    // 返回值即宿主写入的缓冲区，调用返回后仍然有效，释放返回值后无残留分配
+    let before = live_allocations();
+    let msg = host.call(req).unwrap();
+    assert_eq!(live_allocations(), before + 1);
+    assert_eq!(msg.as_ptr() as usize, WRITTEN.with(Cell::get));
+    assert_eq!(msg, expected.as_bytes());
+    drop(msg);
+    assert_eq!(live_allocations(), before);
+}
+
+#[test]
+fn response_is_read_by_reported_length() {
+    unsafe extern "C" fn sys_call(_: i32, _: *const c_char) -> usize {
+        // 宿主将结尾 NUL 计入长度
+        br#"{"rc":0,"tt":7}"#.len() + 1
+    }
+
+    unsafe extern "C" fn sys_msg(msg_len: i32, msg_ptr: *mut c_char) -> *mut c_char {
+        let msg = b"{\"rc\":0,\"tt\":7}\0";
+        assert_eq!(msg.len(), msg_len as usize);
+        ptr::copy_nonoverlapping(msg.as_ptr(), msg_ptr as *mut u8, msg.len());
+        msg_ptr
+    }
+
+    let service = LedgerService::new(WasmHost::with_imports(sys_call, sys_msg));
+    assert_eq!(service.get_tx_time().unwrap(), 7);
+}
+
+#[test]
+fn foreign_response_buffer_is_rejected() {
+    unsafe extern "C" fn sys_call(_: i32, _: *const c_char) -> usize {
+        br#"{"rc":0,"tt":7}"#.len()
+    }
+
+    // 宿主未写入合约分配的缓冲区，返回其它内存
+    unsafe extern "C" fn sys_msg(_: i32, _: *mut c_char) -> *mut c_char {
+        static MSG: &[u8] = br#"{"rc":0,"tt":7}"#;
+        MSG.as_ptr() as *mut c_char
+    }
+
+    unsafe extern "C" fn null_sys_msg(_: i32, _: *mut c_char) -> *mut c_char {
+        ptr::null_mut()
+    }
+
+    for host in [WasmHost::with_imports(sys_call, sys_msg), WasmHost::with_imports(sys_call, null_sys_msg)] {
+        assert!(matches!(host.call(br#"{"rt":4}"#), Err(ContractError::Decode(_))));
+        assert!(matches!(LedgerService::new(host).get_tx_time(), Err(ContractError::Decode(_))));
+    }
+}
+
+#[test]
+fn binary_value_round_trips_through_host() {
+    let service = LedgerService::new(mock_host());
+    let account = common::account();
+    let value = b"\0head\0\xff\0tail\0".to_vec();
+    assert_eq!(service.set_bytes(&account, "bin", &value).unwrap(), 0);
+    assert_eq!(service.set_bytes(&account, "bin", b"\0").unwrap(), 1);
+
+    let data = service.get_bytes(&account, "bin", 0).unwrap().unwrap();
+    assert_eq!((data.key.as_str(), data.value, data.version), ("bin", value, 0));
+    assert_eq!(service.get_bytes(&account, "bin", -1).unwrap().unwrap().value, b"\0");
+    assert!(service.get_bytes(&account, "none", -1).unwrap().is_none());
+}
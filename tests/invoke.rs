@@ -0,0 +1,107 @@
+// 基于 MockLedger 校验跨合约调用的参数编码、返回值及嵌套调用
+
+mod common;
+
+use jdchain_rust_contract::jdcc_api::LedgerService;
+use jdchain_rust_contract::jdcc_mock::MockLedger;
+use jdchain_rust_contract::jdcc_types::{ContractError, DataValue, ErrorCode, Message, Response, ResultCode};
+
+use common::{account, contract};
+
+// 余额合约：balance/transfer 读写数据账户，echo 原样返回参数
+fn token(ledger: &MockLedger, method: &str, args: Vec<DataValue>) -> Result<Option<DataValue>, ContractError> {
+    let service = LedgerService::new(ledger);
+    let account = account();
+    match (method, args.as_slice()) {
+        ("balance", [DataValue::Text(owner)]) => {
+            let balance = service.get_value(&account, owner, -1)?.and_then(|data| data.value.as_int64()).unwrap_or(0);
+            Ok(Some(DataValue::int64(balance)))
+        }
+        ("mint", [DataValue::Text(owner), DataValue::Int64(amount)]) => {
+            service.update_int64(&account, owner, |old| old.unwrap_or(0) + amount)?;
+            Ok(None)
+        }
+        ("echo", [arg, ..]) => Ok(Some(arg.clone())),
+        ("whoami", []) => Ok(Some(DataValue::text(service.get_contract_address()?.to_string()))),
+        _ => Err(ContractError::Decode(format!("unknown method {}", method))),
+    }
+}
+
+fn ledger() -> MockLedger {
+    let token_address = contract("token");
+    common::ledger()
+        .with_contract(&token_address, token)
+        // 兑换合约：通过余额合约铸币后返回最新余额
+        .with_contract(&contract("exchange"), move |ledger, method, args| {
+            let service = LedgerService::new(ledger);
+            match method {
+                "buy" => {
+                    service.invoke_contract(&token_address, "mint", &args)?;
+                    Ok(service.invoke_contract(&token_address, "balance", &args[..1])?.into_value())
+                }
+                _ => Err(ContractError::Decode(format!("unknown method {}", method))),
+            }
+        })
+}
+
+#[test]
+fn arguments_and_return_values_keep_their_types() {
+    let ledger = ledger();
+    let service = LedgerService::new(&ledger);
+    let token = contract("token");
+
+    for arg in [DataValue::text("alice"), DataValue::int64(-3), DataValue::bytes(vec![0u8, 1, 2]), DataValue::image(Vec::new())] {
+        let ret = service.invoke_contract(&token, "echo", &[arg.clone(), DataValue::bytes(vec![9u8; 4])]).unwrap();
+        assert_eq!(ret.value(), Some(&arg));
+    }
+    assert!(service.invoke_contract(&token, "mint", &["alice".into(), 5.into()]).unwrap().is_empty());
+    assert_eq!(service.invoke_contract(&token, "balance", &["alice".into()]).unwrap().as_int64(), Some(5));
+}
+
+#[test]
+fn nested_invocations_switch_contract_address() {
+    let ledger = ledger();
+    let service = LedgerService::new(&ledger);
+    let caller = service.get_contract_address().unwrap();
+
+    let ret = service.invoke_contract(&contract("exchange"), "buy", &["bob".into(), 7.into()]).unwrap();
+    assert_eq!(ret.as_int64(), Some(7));
+    let ret = service.invoke_contract(&contract("token"), "whoami", &[]).unwrap();
+    assert_eq!(ret.as_text(), Some(contract("token").to_string().as_str()));
+    assert_eq!(service.get_contract_address().unwrap(), caller);
+}
+
+#[test]
+fn cached_writes_are_visible_to_invoked_contracts() {
+    let ledger = ledger();
+    let service = LedgerService::new(&ledger).with_overlay();
+    let token = contract("token");
+
+    service.set_int64(&account(), "carol", 10).unwrap();
+    service.invoke_contract(&token, "mint", &["carol".into(), 1.into()]).unwrap();
+    assert_eq!(service.get_value(&account(), "carol", -1).unwrap().unwrap().value, DataValue::int64(11));
+}
+
+#[test]
+fn failures_are_reported_to_caller() {
+    let ledger = ledger();
+    let service = LedgerService::new(&ledger);
+
+    match service.invoke_contract(&contract("missing"), "balance", &[]) {
+        Err(e) => assert_eq!((e.code(), e.sub_code()), (Some(ResultCode::NotFound), Some(ErrorCode::AccountNotFound))),
+        other => panic!("unexpected result {:?}", other),
+    }
+    match service.invoke_contract(&contract("token"), "burn", &[]) {
+        Err(e) => assert_eq!(e.code(), Some(ResultCode::Error)),
+        other => panic!("unexpected result {:?}", other),
+    }
+}
+
+#[test]
+fn oversized_argument_lengths_are_rejected() {
+    let ledger = ledger();
+    let header = format!(r#"{{"rt":27,"a":"{}","m":"echo","ps":[{{"t":"BYTES","l":1}},{{"t":"BYTES","l":{}}}]}}"#, contract("token"), usize::MAX);
+    let ret = ledger.handle(&Message::with_payload(header, vec![1u8, 2]).encode());
+    let response: Response = serde_json::from_slice(&ret).unwrap();
+    assert_eq!((response.rc, response.ec), (ResultCode::IllegalArgument, Some(ErrorCode::InvalidValue)));
+}
@@ -0,0 +1,58 @@
+// 基于 MockLedger 校验区块、参与方及账本配置查询
+
+use jdchain_rust_contract::jdcc_api::LedgerService;
+use jdchain_rust_contract::jdcc_mock::MockLedger;
+use jdchain_rust_contract::jdcc_types::{CryptoAlgorithm, HashDigest, LedgerSettings, Participant, ParticipantState, PubKey};
+
+fn participant(id: i32, state: ParticipantState) -> Participant {
+    let pubkey = PubKey::new(0x4115, HashDigest::sha256(&id.to_be_bytes()).digest());
+    Participant { id, name: format!("peer{}", id), address: pubkey.address(), pubkey, state }
+}
+
+#[test]
+fn default_ledger_metadata() {
+    let ledger = MockLedger::new();
+    let service = LedgerService::new(&ledger);
+
+    assert_eq!(service.get_latest_block_height().unwrap(), 0);
+    let participants = service.get_participants().unwrap();
+    assert_eq!(participants.len(), 4);
+    assert_eq!(service.get_consensus_nodes().unwrap(), participants);
+    assert!(participants.iter().all(|node| node.pubkey.address() == node.address));
+
+    let settings = service.get_ledger_settings().unwrap();
+    assert_eq!(settings.hash_algorithm, CryptoAlgorithm::new(0x2018, "SHA256"));
+    assert!(settings.supports(0x4115) && settings.supports(0xC102));
+    assert!(settings.auto_verify_hash);
+}
+
+#[test]
+fn configured_ledger_metadata() {
+    let block_hash = HashDigest::sha256(b"block 12");
+    let participants = vec![
+        participant(0, ParticipantState::Consensus),
+        participant(1, ParticipantState::Ready),
+        participant(2, ParticipantState::Consensus),
+        participant(3, ParticipantState::Deactivated),
+    ];
+    let settings = LedgerSettings {
+        consensus_provider: "com.jd.blockchain.consensus.raft.RaftConsensusProvider".to_string(),
+        hash_algorithm: CryptoAlgorithm::new(0x2003, "SM3"),
+        crypto_algorithms: vec![CryptoAlgorithm::new(0xC102, "SM2"), CryptoAlgorithm::new(0x2003, "SM3")],
+        auto_verify_hash: false,
+    };
+    let ledger = MockLedger::new()
+        .with_latest_block(12, &block_hash)
+        .with_participants(&participants)
+        .with_ledger_settings(&settings);
+    let service = LedgerService::new(&ledger);
+
+    assert_eq!(service.get_latest_block_height().unwrap(), 12);
+    assert_eq!(service.get_latest_block_hash().unwrap(), block_hash);
+    assert_eq!(service.get_participants().unwrap(), participants);
+    let nodes: Vec<i32> = service.get_consensus_nodes().unwrap().iter().map(|node| node.id).collect();
+    assert_eq!(nodes, vec![0, 2]);
+    let fetched = service.get_ledger_settings().unwrap();
+    assert_eq!(fetched, settings);
+    assert!(!fetched.supports(0x4115));
+}
@@ -0,0 +1,70 @@
+// 校验二进制帧编解码及含 NUL 字节数据经 MockLedger 的读写
+
+mod common;
+
+use jdchain_rust_contract::jdcc_api::LedgerService;
+use jdchain_rust_contract::jdcc_types::{ContractError, Message};
+
+fn assert_decode_error(data: Vec<u8>) {
+    match Message::decode(data) {
+        Err(ContractError::Decode(_)) => {}
+        Err(e) => panic!("unexpected error {:?}", e),
+        Ok(msg) => panic!("unexpected message {}", msg.header),
+    }
+}
+
+#[test]
+fn binary_value_round_trips_through_mock() {
+    let ledger = common::ledger();
+    let service = LedgerService::new(&ledger);
+    let account = common::account();
+    let values: [&[u8]; 4] = [b"\0", b"\0\0\0", b"a\0b\0", b""];
+    for (i, value) in values.iter().enumerate() {
+        assert_eq!(service.set_bytes(&account, "bin", value).unwrap(), i as i64);
+    }
+    for (i, value) in values.iter().enumerate() {
+        assert_eq!(service.get_bytes(&account, "bin", i as i64).unwrap().unwrap().value, *value);
+    }
+    assert_eq!(service.get_bytes(&account, "bin", -1).unwrap().unwrap().version, 3);
+}
+
+#[test]
+fn frame_round_trips_payload() {
+    let frame = Message::with_payload(r#"{"rc":0}"#.to_string(), b"\0x\0".to_vec()).encode();
+    assert_eq!(frame[0], 0);
+    let msg = Message::decode(frame).unwrap();
+    assert_eq!(msg.header, r#"{"rc":0}"#);
+    assert_eq!(msg.payload.unwrap(), b"\0x\0");
+
+    let msg = Message::decode(Message::json(r#"{"rc":0}"#.to_string()).encode()).unwrap();
+    assert_eq!(msg.header, r#"{"rc":0}"#);
+    assert!(msg.payload.is_none());
+}
+
+#[test]
+fn frame_rejects_truncated_and_trailing_bytes() {
+    let frame = Message::with_payload(r#"{"rc":0}"#.to_string(), b"payload".to_vec()).encode();
+    for len in [1, 3, 5, 12, 14, frame.len() - 1] {
+        assert_decode_error(frame[..len].to_vec());
+    }
+    let mut trailing = frame.clone();
+    trailing.push(0);
+    assert_decode_error(trailing);
+
+    // 头部长度超出帧长度
+    let mut oversized = frame;
+    oversized[1..5].copy_from_slice(&u32::MAX.to_le_bytes());
+    assert_decode_error(oversized);
+}
+
+#[test]
+fn json_message_drops_trailing_nul() {
+    let msg = Message::decode(b"{\"rc\":0}\0".to_vec()).unwrap();
+    assert_eq!(msg.header, r#"{"rc":0}"#);
+    assert!(msg.payload.is_none());
+
+    match Message::decode(b"{\"rc\":0}\xff".to_vec()) {
+        Err(ContractError::InvalidUtf8) => {}
+        other => panic!("unexpected result {:?}", other.map(|msg| msg.header)),
+    }
+}
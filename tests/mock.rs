@@ -0,0 +1,67 @@
+// 校验 MockLedger 构建配置及带版本写入的版本冲突规则
+
+mod common;
+
+use jdchain_rust_contract::jdcc_api::LedgerService;
+use jdchain_rust_contract::jdcc_mock::MockLedger;
+use jdchain_rust_contract::jdcc_types::{ContractError, ErrorCode, HashDigest, ResultCode};
+
+use common::{account, pubkey, user};
+
+#[test]
+fn builder_configures_transaction_context() {
+    let ledger = MockLedger::new()
+        .with_ledger_hash(&HashDigest::sha256(b"ledger"))
+        .with_contract_address(&user("contract"))
+        .with_tx_hash(&HashDigest::sha256(b"tx"))
+        .with_tx_time(1700000000000)
+        .with_signers(&[user("alice"), user("bob")])
+        .with_user(&user("carol"), &pubkey());
+    let service = LedgerService::new(&ledger);
+
+    assert_eq!(service.get_ledger_hash().unwrap(), HashDigest::sha256(b"ledger"));
+    assert_eq!(service.get_contract_address().unwrap(), user("contract"));
+    assert_eq!(service.get_tx_hash().unwrap(), HashDigest::sha256(b"tx"));
+    assert_eq!(service.get_tx_time().unwrap(), 1700000000000);
+    assert_eq!(service.get_signers().unwrap(), vec![user("alice"), user("bob")]);
+    assert_eq!(service.get_user(&user("carol")).unwrap().unwrap().pubkey, pubkey());
+    assert!(service.get_user(&user("dave")).unwrap().is_none());
+}
+
+#[test]
+fn writes_require_registered_data_account() {
+    let ledger = MockLedger::new();
+    let service = LedgerService::new(&ledger);
+    let err = service.set_text(&account(), "name", "alice").unwrap_err();
+    assert_eq!((err.code(), err.sub_code()), (Some(ResultCode::NotFound), Some(ErrorCode::AccountNotFound)));
+
+    let ledger = ledger.with_data_account(&account(), &pubkey());
+    let service = LedgerService::new(&ledger);
+    assert_eq!(service.get_data_account(&account()).unwrap().unwrap().pubkey, pubkey());
+    assert_eq!(service.set_text(&account(), "name", "alice").unwrap(), 0);
+}
+
+#[test]
+fn versioned_writes_must_match_latest_version() {
+    let ledger = common::ledger();
+    let service = LedgerService::new(&ledger);
+    let account = account();
+
+    // 数据不存在时最新版本为 -1
+    assert!(matches!(service.set_int64_with_version(&account, "n", 1, 0), Err(ContractError::VersionConflict { version: 0, .. })));
+    assert_eq!(service.set_int64_with_version(&account, "n", 1, -1).unwrap(), 0);
+    assert_eq!(service.set_int64_with_version(&account, "n", 2, 0).unwrap(), 1);
+    for version in [-1, 0, 2] {
+        match service.set_int64_with_version(&account, "n", 3, version) {
+            Err(ContractError::VersionConflict { key, version: conflict }) => assert_eq!((key.as_str(), conflict), ("n", version)),
+            other => panic!("unexpected result {:?} for version {}", other, version),
+        }
+    }
+    // 不带版本写入总在最新版本之后追加
+    assert_eq!(service.set_int64(&account, "n", 4).unwrap(), 2);
+    assert_eq!(service.get_value_version(&account, "n").unwrap(), 2);
+
+    // 冲突响应携带版本冲突细分编码
+    let response = String::from_utf8(ledger.handle(format!(r#"{{"rt":15,"a":"{}","k":"n","v":5,"ver":0}}"#, account).as_bytes())).unwrap();
+    assert!(response.starts_with(r#"{"rc":1,"ec":1,"#), "{}", response);
+}
@@ -0,0 +1,112 @@
+// 统计宿主调用次数，校验 KV 读写缓存对重复读取的缓存及写入后版本的一致性
+
+mod common;
+
+use jdchain_rust_contract::jdcc_api::{HostBackend, LedgerService};
+use jdchain_rust_contract::jdcc_types::{ContractError, DataValue};
+
+use common::{account, ledger, CountingHost};
+
+// 依次执行同一组写入，返回各写入结果及最终数据
+fn write_sequence<B: HostBackend>(service: &LedgerService<B>) -> Vec<String> {
+    let account = account();
+    let mut results = vec![
+        service.set_int64(&account, "counter", 1),
+        service.set_int64_with_version(&account, "counter", 2, 0),
+        service.set_int64_with_version(&account, "counter", 3, 1),
+        service.set_int64_with_version(&account, "counter", 4, 0),
+        service.set_text(&account, "name", "alice"),
+        service.set_text(&account, "name", "bob"),
+        service.update_int64(&account, "counter", |old| old.unwrap_or(0) + 1),
+        service.get_value_version(&account, "counter"),
+    ]
+    .into_iter()
+    .map(|ret| format!("{:?}", ret))
+    .collect::<Vec<_>>();
+    for data in service.get_data_entries(&account, 0, 10).unwrap() {
+        results.push(format!("{} {:?} {}", data.key, data.value, data.version));
+    }
+    results
+}
+
+#[test]
+fn repeated_reads_are_served_from_overlay() {
+    let ledger = ledger();
+    let account = account();
+    LedgerService::new(&ledger).set_text(&account, "name", "alice").unwrap();
+    let host = CountingHost::new(&ledger);
+    let service = LedgerService::new(&host).with_overlay();
+
+    for _ in 0..3 {
+        let data = service.get_value(&account, "name", -1).unwrap().unwrap();
+        assert_eq!(data.value, DataValue::text("alice"));
+        assert_eq!(service.get_value_version(&account, "name").unwrap(), 0);
+    }
+    assert!(service.get_value(&account, "missing", -1).unwrap().is_none());
+    assert!(service.get_value(&account, "missing", -1).unwrap().is_none());
+    assert_eq!(host.take(), 2);
+}
+
+#[test]
+fn writes_are_committed_and_cached() {
+    let ledger = ledger();
+    let account = account();
+    let host = CountingHost::new(&ledger);
+    let service = LedgerService::new(&host).with_overlay();
+
+    // 写入不额外查询版本
+    assert_eq!(service.set_text(&account, "name", "alice").unwrap(), 0);
+    assert_eq!(host.take(), 1);
+    for _ in 0..5 {
+        service.update_int64(&account, "counter", |old| old.unwrap_or(0) + 1).unwrap();
+    }
+    // 仅首次读取查询宿主，其余读取由写入后的缓存返回
+    assert_eq!(host.take(), 6);
+    let counter = service.get_value(&account, "counter", -1).unwrap().unwrap();
+    assert_eq!((counter.value, counter.version), (DataValue::int64(5), 4));
+    assert_eq!(service.get_value(&account, "name", -1).unwrap().unwrap().value, DataValue::text("alice"));
+    assert_eq!(host.take(), 0);
+
+    let direct = LedgerService::new(&ledger);
+    let counter = direct.get_value(&account, "counter", -1).unwrap().unwrap();
+    assert_eq!((counter.value, counter.version), (DataValue::int64(5), 4));
+}
+
+#[test]
+fn versions_match_without_overlay() {
+    let plain = ledger();
+    let cached = ledger();
+    let expected = write_sequence(&LedgerService::new(&plain));
+    assert_eq!(write_sequence(&LedgerService::new(&cached).with_overlay()), expected);
+    assert_eq!(expected[2], "Ok(2)");
+    assert!(expected[3].starts_with("Err(VersionConflict"));
+}
+
+#[test]
+fn conflicting_writes_refresh_cache() {
+    let ledger = ledger();
+    let account = account();
+    let service = LedgerService::new(&ledger).with_overlay();
+
+    assert!(service.get_value(&account, "name", -1).unwrap().is_none());
+    LedgerService::new(&ledger).set_text(&account, "name", "bob").unwrap();
+    match service.set_text_with_version(&account, "name", "alice", -1) {
+        Err(ContractError::VersionConflict { version: -1, .. }) => {}
+        other => panic!("unexpected result {:?}", other),
+    }
+    let data = service.get_value(&account, "name", -1).unwrap().unwrap();
+    assert_eq!((data.value, data.version), (DataValue::text("bob"), 0));
+}
+
+#[test]
+fn discard_drops_cached_reads() {
+    let ledger = ledger();
+    let account = account();
+    let service = LedgerService::new(&ledger).with_overlay();
+
+    service.set_text(&account, "name", "alice").unwrap();
+    LedgerService::new(&ledger).set_text(&account, "name", "bob").unwrap();
+    assert_eq!(service.get_value(&account, "name", -1).unwrap().unwrap().value, DataValue::text("alice"));
+    service.discard();
+    assert_eq!(service.get_value(&account, "name", -1).unwrap().unwrap().value, DataValue::text("bob"));
+}
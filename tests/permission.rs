@@ -0,0 +1,97 @@
+// 基于 MockLedger 校验角色配置、用户授权及多角色权限合并
+
+mod common;
+
+use jdchain_rust_contract::jdcc_api::LedgerService;
+use jdchain_rust_contract::jdcc_mock::MockLedger;
+use jdchain_rust_contract::jdcc_types::{
+    Address, ContractError, ErrorCode, LedgerPermission, PubKey, ResultCode, RoleConfig, RolesPolicy, TransactionPermission,
+};
+
+fn admin() -> (Address, PubKey) {
+    let pubkey = common::pubkey();
+    (pubkey.address(), pubkey)
+}
+
+fn ledger() -> (MockLedger, Address) {
+    let (admin, pubkey) = admin();
+    let ledger = MockLedger::new()
+        .with_user(&admin, &pubkey)
+        .with_role(&RoleConfig::new("ADMIN").enable(&[LedgerPermission::ConfigureRoles, LedgerPermission::AuthorizeUserRoles]))
+        .with_user_roles(&admin, &["ADMIN"], RolesPolicy::Union)
+        .with_signers(std::slice::from_ref(&admin));
+    (ledger, admin)
+}
+
+fn assert_denied<T: std::fmt::Debug>(ret: Result<T, ContractError>) {
+    match ret {
+        Err(e) => assert_eq!((e.code(), e.sub_code()), (Some(ResultCode::Unauthorized), Some(ErrorCode::PermissionDenied))),
+        other => panic!("unexpected result {:?}", other),
+    }
+}
+
+#[test]
+fn configure_roles_and_query_privileges() {
+    let (ledger, _) = ledger();
+    let service = LedgerService::new(&ledger);
+
+    assert_eq!(service.get_role_privilege("WRITER").unwrap(), None);
+    service.create_role("WRITER", &[LedgerPermission::WriteDataAccount, LedgerPermission::RegisterDataAccount], &[TransactionPermission::ContractOperation]).unwrap();
+    service.configure_role(&RoleConfig::new("WRITER").disable(&[LedgerPermission::RegisterDataAccount])).unwrap();
+
+    let role = service.get_role_privilege("WRITER").unwrap().unwrap();
+    assert_eq!(role.ledger_permissions, vec![LedgerPermission::WriteDataAccount]);
+    assert!(role.has_transaction_permission(TransactionPermission::ContractOperation));
+    assert!(!role.has_transaction_permission(TransactionPermission::DirectOperation));
+}
+
+#[test]
+fn authorized_roles_are_merged_by_policy() {
+    let (ledger, _) = ledger();
+    let service = LedgerService::new(&ledger);
+    let user = service.register_user("alice").unwrap();
+    service.create_role("WRITER", &[LedgerPermission::WriteDataAccount, LedgerPermission::WriteEventAccount], &[]).unwrap();
+    service.create_role("PUBLISHER", &[LedgerPermission::WriteEventAccount], &[TransactionPermission::ContractOperation]).unwrap();
+
+    service.authorize_user(&user, &["WRITER", "PUBLISHER"], RolesPolicy::Intersect).unwrap();
+    let roles = service.get_user_roles(&user).unwrap();
+    assert_eq!((roles.roles, roles.policy), (vec!["PUBLISHER".to_string(), "WRITER".to_string()], RolesPolicy::Intersect));
+    let privilege = service.get_user_privilege(&user).unwrap();
+    assert_eq!(privilege.ledger_permissions, vec![LedgerPermission::WriteEventAccount]);
+    assert!(privilege.transaction_permissions.is_empty());
+
+    service.unauthorize_user(&user, &["WRITER"]).unwrap();
+    let privilege = service.get_user_privilege(&user).unwrap();
+    assert_eq!(privilege.roles, vec!["PUBLISHER".to_string()]);
+    assert!(privilege.has_transaction_permission(TransactionPermission::ContractOperation));
+    assert_eq!(service.get_user_roles(&user).unwrap().policy, RolesPolicy::Intersect);
+}
+
+#[test]
+fn management_requires_signer_permission() {
+    let (ledger, admin) = ledger();
+    let service = LedgerService::new(&ledger);
+    let user = service.register_user("bob").unwrap();
+    let ledger = ledger.with_signers(std::slice::from_ref(&user));
+    let service = LedgerService::new(&ledger);
+
+    assert_denied(service.create_role("WRITER", &[LedgerPermission::WriteDataAccount], &[]));
+    assert_denied(service.authorize_user(&user, &["ADMIN"], RolesPolicy::Union));
+    assert!(service.get_user_privilege(&admin).unwrap().has_ledger_permission(LedgerPermission::ConfigureRoles));
+}
+
+#[test]
+fn unknown_users_and_roles_are_rejected() {
+    let (ledger, admin) = ledger();
+    let service = LedgerService::new(&ledger);
+    let stranger = PubKey::new(0x4115, &[7u8; 32]).address();
+
+    match service.get_user_roles(&stranger) {
+        Err(e) => assert_eq!(e.sub_code(), Some(ErrorCode::AccountNotFound)),
+        other => panic!("unexpected result {:?}", other),
+    }
+    match service.authorize_user(&admin, &["MISSING"], RolesPolicy::Union) {
+        Err(e) => assert_eq!(e.sub_code(), Some(ErrorCode::KeyNotFound)),
+        other => panic!("unexpected result {:?}", other),
+    }
+}
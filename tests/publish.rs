@@ -0,0 +1,58 @@
+// 基于 MockLedger 校验事件账户注册、事件发布及最新事件查询
+
+mod common;
+
+use jdchain_rust_contract::jdcc_api::LedgerService;
+use jdchain_rust_contract::jdcc_mock::MockLedger;
+use jdchain_rust_contract::jdcc_types::{ContractError, DataValue, ErrorCode};
+
+#[test]
+fn events_are_published_in_sequence() {
+    let ledger = MockLedger::new();
+    let service = LedgerService::new(&ledger);
+    let account = service.register_event_account("events").unwrap();
+    match service.register_event_account("events") {
+        Err(e) => assert_eq!(e.sub_code(), Some(ErrorCode::AccountExists)),
+        Ok(address) => panic!("unexpected address {}", address),
+    }
+
+    assert_eq!(service.publish_event(&account, "order", DataValue::text("created"), -1).unwrap(), 0);
+    assert_eq!(service.publish_event(&account, "order", DataValue::bytes(&b"\0paid"[..]), 0).unwrap(), 1);
+    let event = service.get_latest_event(&account, "order").unwrap().unwrap();
+    assert_eq!((event.account, event.topic.as_str(), event.sequence), (account.clone(), "order", 1));
+    assert_eq!(event.content, DataValue::Bytes(b"\0paid".to_vec()));
+}
+
+#[test]
+fn sequence_conflict_is_version_conflict() {
+    let ledger = MockLedger::new().with_event_account(&common::account());
+    let service = LedgerService::new(&ledger);
+    let account = common::account();
+    service.publish_event(&account, "order", DataValue::text("created"), -1).unwrap();
+
+    for sequence in [-1, 1, 5] {
+        match service.publish_event(&account, "order", DataValue::text("stale"), sequence) {
+            Err(ContractError::VersionConflict { key, version }) => assert_eq!((key.as_str(), version), ("order", sequence)),
+            other => panic!("unexpected result {:?}", other),
+        }
+    }
+    let event = service.get_latest_event(&account, "order").unwrap().unwrap();
+    assert_eq!((event.sequence, event.content), (0, DataValue::text("created")));
+}
+
+#[test]
+fn latest_event_on_missing_topic_or_account() {
+    let ledger = MockLedger::new().with_event_account(&common::account());
+    let service = LedgerService::new(&ledger);
+
+    assert!(service.get_latest_event(&common::account(), "none").unwrap().is_none());
+    match service.get_latest_event(&common::user("missing"), "order") {
+        Err(e) => assert_eq!(e.sub_code(), Some(ErrorCode::AccountNotFound), "{}", e),
+        Ok(event) => panic!("unexpected event {:?}", event.map(|event| event.topic)),
+    }
+    // 未注册的事件账户无法发布
+    match service.publish_event(&common::user("missing"), "order", DataValue::text("created"), -1) {
+        Err(e) => assert_eq!(e.sub_code(), Some(ErrorCode::AccountNotFound), "{}", e),
+        Ok(sequence) => panic!("unexpected sequence {}", sequence),
+    }
+}
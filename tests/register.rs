@@ -0,0 +1,90 @@
+// 基于 MockLedger 校验按签名算法及公钥注册用户、数据账户
+
+mod common;
+
+use jdchain_rust_contract::jdcc_api::LedgerService;
+use jdchain_rust_contract::jdcc_mock::MockLedger;
+use jdchain_rust_contract::jdcc_types::{Address, Algorithm, ContractError, ErrorCode, PubKey};
+
+fn assert_exists(ret: Result<Address, ContractError>) {
+    match ret {
+        Err(e) => assert_eq!(e.sub_code(), Some(ErrorCode::AccountExists), "{}", e),
+        Ok(address) => panic!("unexpected address {}", address),
+    }
+}
+
+#[test]
+fn algorithm_parses_names() {
+    for algorithm in [Algorithm::Ed25519, Algorithm::Sm2, Algorithm::Ecdsa, Algorithm::Rsa] {
+        assert_eq!(algorithm.name().parse::<Algorithm>().unwrap(), algorithm);
+        assert_eq!(Algorithm::from_code(algorithm.code()), Some(algorithm));
+    }
+    assert_eq!("sm2".parse::<Algorithm>().unwrap(), Algorithm::Sm2);
+    assert_eq!("Ed25519".parse::<Algorithm>().unwrap(), Algorithm::Ed25519);
+    for name in ["", "SM3", "SHA256", "ED 25519"] {
+        assert!(matches!(name.parse::<Algorithm>(), Err(ContractError::Decode(_))), "{:?}", name);
+    }
+    assert_eq!(Algorithm::from_code(0x2018), None);
+    assert_eq!(Algorithm::default(), Algorithm::Ed25519);
+}
+
+#[test]
+fn registered_accounts_use_chosen_algorithm() {
+    let ledger = MockLedger::new();
+    let service = LedgerService::new(&ledger);
+
+    let user = service.register_user_with("alice", Algorithm::Sm2).unwrap();
+    assert_eq!(user.signature_algorithm(), Some(Algorithm::Sm2));
+    let pubkey = service.get_user(&user).unwrap().unwrap().pubkey;
+    assert_eq!(pubkey.signature_algorithm(), Some(Algorithm::Sm2));
+    assert_eq!(pubkey.address(), user);
+
+    let account = service.register_data_account_with("alice", Algorithm::Sm2).unwrap();
+    assert_eq!(account.signature_algorithm(), Some(Algorithm::Sm2));
+    let pubkey = service.get_data_account(&account).unwrap().unwrap().pubkey;
+    assert_eq!(pubkey.signature_algorithm(), Some(Algorithm::Sm2));
+    assert_eq!(pubkey.address(), account);
+
+    // 默认 ED25519，同一 seed 不同算法生成不同地址
+    let default = service.register_user("alice").unwrap();
+    assert_eq!(default.signature_algorithm(), Some(Algorithm::Ed25519));
+    assert_ne!(default, user);
+    assert_eq!(service.register_data_account("alice").unwrap().signature_algorithm(), Some(Algorithm::Ed25519));
+}
+
+#[test]
+fn duplicate_registration_is_rejected() {
+    let ledger = MockLedger::new();
+    let service = LedgerService::new(&ledger);
+
+    let user = service.register_user_with("bob", Algorithm::Ecdsa).unwrap();
+    assert_exists(service.register_user_with("bob", Algorithm::Ecdsa));
+    let account = service.register_data_account_with("bob", Algorithm::Ecdsa).unwrap();
+    assert_exists(service.register_data_account_with("bob", Algorithm::Ecdsa));
+
+    assert_eq!(service.get_user(&user).unwrap().unwrap().address, user);
+    assert_eq!(service.get_data_account(&account).unwrap().unwrap().address, account);
+}
+
+#[test]
+fn registration_from_pubkey_uses_pubkey_address() {
+    let ledger = MockLedger::new();
+    let service = LedgerService::new(&ledger);
+    let pubkey = common::pubkey();
+
+    let user = service.register_user_with_pubkey(&pubkey).unwrap();
+    assert_eq!(user, pubkey.address());
+    assert_eq!(user, common::account());
+    assert_eq!(service.get_user(&user).unwrap().unwrap().pubkey, pubkey);
+    assert_exists(service.register_user_with_pubkey(&pubkey));
+
+    let account = service.register_data_account_with_pubkey(&pubkey).unwrap();
+    assert_eq!(account, pubkey.address());
+    assert_eq!(service.get_data_account(&account).unwrap().unwrap().pubkey, pubkey);
+    assert_exists(service.register_data_account_with_pubkey(&pubkey));
+
+    // SM2 公钥注册后地址保留算法
+    let sm2 = PubKey::new(Algorithm::Sm2.code(), &[4u8; 65]);
+    let user = service.register_user_with_pubkey(&sm2).unwrap();
+    assert_eq!((user.signature_algorithm(), user), (Some(Algorithm::Sm2), sm2.address()));
+}
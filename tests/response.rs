@@ -0,0 +1,77 @@
+// 通过固定返回数据的宿主校验响应编码、宿主错误信息及返回数据的解析错误
+
+mod common;
+
+use jdchain_rust_contract::jdcc_api::LedgerService;
+use jdchain_rust_contract::jdcc_types::{ContractError, ErrorCode, HostError, ResultCode};
+
+use common::FixedHost;
+
+fn service(response: &str) -> LedgerService<FixedHost> {
+    LedgerService::new(FixedHost(response.as_bytes().to_vec()))
+}
+
+#[test]
+#[allow(deprecated)]
+fn result_codes_round_trip() {
+    use jdchain_rust_contract::jdcc_types::{ERROR, SUCCESS};
+
+    assert_eq!(ResultCode::from(SUCCESS), ResultCode::Success);
+    assert_eq!(ResultCode::from(ERROR), ResultCode::Error);
+    assert_eq!(u8::from(ResultCode::NotFound), 2);
+    assert_eq!(ResultCode::from(42), ResultCode::Unknown(42));
+    assert_eq!(u8::from(ResultCode::Unknown(42)), 42);
+}
+
+#[test]
+fn successful_response_is_decoded() {
+    assert_eq!(service(r#"{"rc":0,"tt":1700000000000}"#).get_tx_time().unwrap(), 1700000000000);
+}
+
+#[test]
+fn malformed_response_is_decode_error() {
+    for response in [r#"{"rc":0,"tt":"#, "not json", r#"{"rc":0,"tt":"soon"}"#, ""] {
+        match service(response).get_tx_time() {
+            Err(ContractError::Decode(_)) => {}
+            other => panic!("unexpected result {:?} for {:?}", other, response),
+        }
+    }
+}
+
+#[test]
+fn missing_field_is_reported_by_name() {
+    match service(r#"{"rc":0}"#).get_tx_time() {
+        Err(ContractError::MissingField("tt")) => {}
+        other => panic!("unexpected result {:?}", other),
+    }
+    match service(r#"{"rc":0,"lh":null}"#).get_ledger_hash() {
+        Err(ContractError::MissingField("lh")) => {}
+        other => panic!("unexpected result {:?}", other),
+    }
+}
+
+#[test]
+fn failed_response_is_host_error() {
+    let err = service(r#"{"rc":4}"#).get_tx_time().unwrap_err();
+    assert_eq!(err.code(), Some(ResultCode::Unauthorized));
+    assert!(matches!(err, ContractError::Host(_)));
+}
+
+#[test]
+fn error_message_and_sub_code_are_decoded() {
+    let err = service(r#"{"rc":1,"ec":5,"em":"role not granted"}"#).get_tx_time().unwrap_err();
+    assert_eq!(err.code(), Some(ResultCode::Error));
+    assert_eq!(err.sub_code(), Some(ErrorCode::PermissionDenied));
+    assert_eq!(err.message(), Some("role not granted"));
+    assert_eq!(err.to_string(), "host returned Error (PermissionDenied): role not granted");
+    match err {
+        ContractError::Host(HostError { code: ResultCode::Error, sub_code: Some(ErrorCode::PermissionDenied), message: Some(message) }) => {
+            assert_eq!(message, "role not granted")
+        }
+        other => panic!("unexpected error {:?}", other),
+    }
+
+    // 未定义的细分编码原样保留，缺省字段为空
+    let err = service(r#"{"rc":1,"ec":99}"#).get_tx_time().unwrap_err();
+    assert_eq!((err.sub_code(), err.message()), (Some(ErrorCode::Unknown(99)), None));
+}
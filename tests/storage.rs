@@ -0,0 +1,193 @@
+// 基于 MockLedger 校验 StorageMap、StorageVec、StorageSet 的读写及长度计数
+
+use serde::{Deserialize, Serialize};
+
+mod common;
+
+use jdchain_rust_contract::jdcc_api::LedgerService;
+use jdchain_rust_contract::jdcc_storage::Codec;
+use jdchain_rust_contract::jdcc_types::{ContractError, DataValue};
+
+use common::{account, ledger};
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+struct Order {
+    id: u64,
+    amount: i64,
+}
+
+#[test]
+fn map_tracks_entries_and_length() {
+    let ledger = ledger();
+    let account = account();
+    let service = LedgerService::new(&ledger);
+    let storage = service.storage(&account);
+    let balances = storage.map::<str, i64>("balance");
+
+    assert!(balances.is_empty().unwrap());
+    assert_eq!(balances.insert("alice", &100).unwrap(), None);
+    assert_eq!(balances.insert("bob", &50).unwrap(), None);
+    assert_eq!(balances.insert("alice", &80).unwrap(), Some(100));
+    assert_eq!(balances.len().unwrap(), 2);
+    assert_eq!(balances.get("alice").unwrap(), Some(80));
+    assert_eq!(balances.get("carol").unwrap(), None);
+
+    assert_eq!(balances.remove("bob").unwrap(), Some(50));
+    assert_eq!(balances.remove("bob").unwrap(), None);
+    assert!(!balances.contains_key("bob").unwrap());
+    assert_eq!(balances.len().unwrap(), 1);
+
+    assert_eq!(balances.insert("bob", &10).unwrap(), None);
+    assert_eq!(balances.len().unwrap(), 2);
+}
+
+#[test]
+fn map_uses_configured_prefix_in_keys() {
+    let ledger = ledger();
+    let account = account();
+    let service = LedgerService::new(&ledger);
+    let storage = service.storage(&account);
+    storage.map::<u64, Order>("orders").insert(&7, &Order { id: 7, amount: 3 }).unwrap();
+
+    let entry = service.get_value(&account, "orders/7", -1).unwrap().unwrap();
+    assert_eq!(entry.value, DataValue::json(&[Order { id: 7, amount: 3 }]).unwrap());
+    let len = service.get_value(&account, "orders.len", -1).unwrap().unwrap();
+    assert_eq!(len.value, DataValue::Json("1".to_string()));
+    assert!(service.get_value(&account, "balance/7", -1).unwrap().is_none());
+}
+
+#[test]
+fn vec_push_pop_and_iterate() {
+    let ledger = ledger();
+    let account = account();
+    let service = LedgerService::new(&ledger);
+    let storage = service.storage(&account).with_codec(Codec::Binary);
+    let orders = storage.vec::<Order>("orders");
+
+    for id in 0..3 {
+        assert_eq!(orders.push(&Order { id, amount: id as i64 * 10 }).unwrap(), id);
+    }
+    assert_eq!(orders.len().unwrap(), 3);
+    assert_eq!(orders.get(1).unwrap(), Some(Order { id: 1, amount: 10 }));
+    assert_eq!(orders.get(3).unwrap(), None);
+
+    orders.set(1, &Order { id: 1, amount: 11 }).unwrap();
+    match orders.set(3, &Order { id: 3, amount: 0 }) {
+        Err(ContractError::IndexOutOfBounds { index: 3, len: 3 }) => {}
+        other => panic!("unexpected result {:?}", other),
+    }
+
+    assert_eq!(orders.pop().unwrap(), Some(Order { id: 2, amount: 20 }));
+    assert_eq!(orders.push(&Order { id: 9, amount: 90 }).unwrap(), 2);
+    let all: Vec<Order> = orders.iter().unwrap().collect::<Result<_, _>>().unwrap();
+    assert_eq!(all, vec![Order { id: 0, amount: 0 }, Order { id: 1, amount: 11 }, Order { id: 9, amount: 90 }]);
+}
+
+#[test]
+fn set_insert_contains_remove() {
+    let ledger = ledger();
+    let account = account();
+    let service = LedgerService::new(&ledger);
+    let storage = service.storage(&account);
+    let members = storage.set_of::<String>("members");
+
+    assert!(members.insert(&"alice".to_string()).unwrap());
+    assert!(!members.insert(&"alice".to_string()).unwrap());
+    assert!(members.contains(&"alice".to_string()).unwrap());
+    assert_eq!(members.len().unwrap(), 1);
+
+    assert!(members.remove(&"alice".to_string()).unwrap());
+    assert!(!members.remove(&"alice".to_string()).unwrap());
+    assert!(!members.contains(&"alice".to_string()).unwrap());
+    assert!(members.is_empty().unwrap());
+}
+
+#[test]
+fn map_writes_on_top_of_latest_version() {
+    let ledger = ledger();
+    let account = account();
+    let service = LedgerService::new(&ledger);
+    let storage = service.storage(&account);
+    let counters = storage.map::<str, u32>("counter");
+
+    counters.insert("a", &1).unwrap();
+    service.set_json(&account, "counter/a", &[5]).unwrap();
+    assert_eq!(counters.insert("a", &2).unwrap(), Some(5));
+    assert_eq!(storage.version("counter/a").unwrap(), 2);
+    assert_eq!(counters.len().unwrap(), 1);
+}
+
+#[test]
+fn remove_entry_written_outside_collection() {
+    let ledger = ledger();
+    let account = account();
+    let service = LedgerService::new(&ledger);
+    let storage = service.storage(&account);
+    service.set_json(&account, "m/a", &[3i64]).unwrap();
+    let map = storage.map::<str, i64>("m");
+
+    assert_eq!(map.len().unwrap(), 0);
+    assert_eq!(map.remove("a").unwrap(), Some(3));
+    assert_eq!(map.len().unwrap(), 0);
+    assert!(!map.contains_key("a").unwrap());
+}
+
+#[test]
+fn vec_slots_missing_within_length_are_none() {
+    let ledger = ledger();
+    let account = account();
+    let service = LedgerService::new(&ledger);
+    let storage = service.storage(&account);
+    let values = storage.vec::<i64>("v");
+    values.push(&7).unwrap();
+    // 长度计数绕过集合直接写入
+    storage.set("v.len", &3u64).unwrap();
+
+    assert_eq!(values.get(0).unwrap(), Some(7));
+    assert_eq!(values.get(1).unwrap(), None);
+    assert_eq!(values.iter().unwrap().collect::<Result<Vec<_>, _>>().unwrap(), vec![7]);
+    assert_eq!(values.pop().unwrap(), None);
+    assert_eq!(values.len().unwrap(), 2);
+}
+
+#[test]
+fn map_entries_are_single_element_lists_with_empty_tombstone() {
+    let ledger = ledger();
+    let account = account();
+    let service = LedgerService::new(&ledger);
+    let storage = service.storage(&account);
+    let map = storage.map::<str, Option<i64>>("m");
+
+    map.insert("a", &None).unwrap();
+    assert_eq!(service.get_json::<Vec<Option<i64>>>(&account, "m/a", -1).unwrap(), Some(vec![None]));
+    assert_eq!(map.get("a").unwrap(), Some(None));
+    assert_eq!(map.remove("a").unwrap(), Some(None));
+    assert_eq!(service.get_json::<Vec<Option<i64>>>(&account, "m/a", -1).unwrap(), Some(vec![]));
+    assert_eq!(map.get("a").unwrap(), None);
+    assert_eq!(map.len().unwrap(), 0);
+}
+
+#[test]
+fn keys_of_different_prefixes_do_not_alias() {
+    let ledger = ledger();
+    let account = account();
+    let service = LedgerService::new(&ledger);
+    let storage = service.storage(&account);
+    let outer = storage.map::<str, i64>("p");
+    let inner = storage.map::<str, i64>("p/q");
+
+    inner.insert("a", &1).unwrap();
+    assert_eq!(outer.get("q.len").unwrap(), None);
+    assert_eq!(outer.get("q/a").unwrap(), None);
+    outer.insert("q.len", &7).unwrap();
+    outer.insert("q/a", &8).unwrap();
+    assert_eq!(inner.len().unwrap(), 1);
+    assert_eq!(inner.get("a").unwrap(), Some(1));
+    assert_eq!((outer.get("q.len").unwrap(), outer.get("q/a").unwrap()), (Some(7), Some(8)));
+
+    let entry = service.get_value(&account, "p/q%2Elen", -1).unwrap().unwrap();
+    assert_eq!(entry.value, DataValue::json(&[7]).unwrap());
+    assert!(service.get_value(&account, "p/q%2Fa", -1).unwrap().is_some());
+    outer.insert("100%", &9).unwrap();
+    assert!(service.get_value(&account, "p/100%25", -1).unwrap().is_some());
+}
@@ -0,0 +1,101 @@
+// 校验 Address、HashDigest、PubKey 的 Base58 解析、格式校验及地址生成
+
+mod common;
+
+use std::ffi::CString;
+
+use sha2::{Digest, Sha256};
+
+use jdchain_rust_contract::jdcc_ffi::FromAbi;
+use jdchain_rust_contract::jdcc_types::{Address, Algorithm, ContractError, HashDigest, PubKey};
+
+use common::{ADDRESS, PUBKEY};
+
+// 重新计算地址校验码后编码
+fn encode_address(mut body: Vec<u8>) -> String {
+    let hash = Sha256::digest(Sha256::digest(&body));
+    body.extend_from_slice(&hash[..4]);
+    bs58::encode(body).into_string()
+}
+
+fn assert_invalid<T: std::fmt::Debug>(ret: Result<T, ContractError>) {
+    match ret {
+        Err(ContractError::InvalidBase58(_)) => {}
+        other => panic!("unexpected result {:?}", other),
+    }
+}
+
+#[test]
+fn pubkey_address_matches_jd_chain() {
+    let pubkey = PubKey::parse(PUBKEY).unwrap();
+    assert_eq!(pubkey.signature_algorithm(), Some(Algorithm::Ed25519));
+    assert_eq!(pubkey.raw_key().len(), 32);
+    let address = pubkey.address();
+    assert_eq!(address.as_str(), ADDRESS);
+    assert_eq!(address, Address::parse(ADDRESS).unwrap());
+    assert_eq!(address.algorithm(), 0x4115);
+    assert_eq!(encode_address(address.as_bytes()[..23].to_vec()), ADDRESS);
+}
+
+#[test]
+fn address_rejects_malformed_input() {
+    let bytes = Address::parse(ADDRESS).unwrap().as_bytes().to_vec();
+
+    let mut bad_checksum = bytes.clone();
+    bad_checksum[26] ^= 0x01;
+    assert_invalid(Address::parse(&bs58::encode(bad_checksum).into_string()));
+
+    let mut bad_version = bytes[..23].to_vec();
+    bad_version[0] = 0x90;
+    assert_invalid(Address::parse(&encode_address(bad_version)));
+
+    assert_invalid(Address::parse(&encode_address(bytes[..22].to_vec())));
+    let mut long = bytes[..23].to_vec();
+    long.push(0);
+    assert_invalid(Address::parse(&encode_address(long)));
+
+    assert_invalid(Address::parse("LdeNgGn7tPYXNi4vAhXN57qAYtb57NvAUDvv0"));
+    assert_invalid(Address::parse("LdeNgGn7tPYXNi4vAhXN57qAYtb57NvAUDvvI"));
+    assert_invalid(Address::parse(""));
+}
+
+#[test]
+fn pubkey_rejects_wrong_key_type() {
+    let mut bytes = PubKey::parse(PUBKEY).unwrap().as_bytes().to_vec();
+    bytes[2] = 0x02;
+    assert_invalid(PubKey::parse(&bs58::encode(&bytes).into_string()));
+    assert_invalid(PubKey::parse(&bs58::encode(&bytes[..3]).into_string()));
+    assert_invalid(PubKey::parse("7VeRG8jpBNg15W7HCrFyLG7TdpUea5jnHAUDbmxAkK6ZYqu0"));
+}
+
+#[test]
+fn hash_digest_checks_length_of_known_algorithms() {
+    let sha256 = HashDigest::sha256(b"data");
+    assert_eq!(HashDigest::parse(sha256.as_str()).unwrap(), sha256);
+
+    let mut short = vec![0x20, 0x18];
+    short.extend_from_slice(&[7u8; 31]);
+    assert_invalid(HashDigest::parse(&bs58::encode(&short).into_string()));
+
+    let mut ripemd160 = vec![0x20, 0x19];
+    ripemd160.extend_from_slice(&[7u8; 20]);
+    assert_eq!(HashDigest::parse(&bs58::encode(&ripemd160).into_string()).unwrap().digest().len(), 20);
+    ripemd160.pop();
+    assert_invalid(HashDigest::parse(&bs58::encode(&ripemd160).into_string()));
+
+    // 未知算法不校验摘要长度
+    assert_eq!(HashDigest::parse(&bs58::encode([0x12, 0x34, 1]).into_string()).unwrap().algorithm(), 0x1234);
+    assert_invalid(HashDigest::parse(&bs58::encode([0x20, 0x18]).into_string()));
+}
+
+#[test]
+fn from_abi_rejects_invalid_address() {
+    let valid = CString::new(ADDRESS).unwrap();
+    let address = unsafe { Address::from_abi(valid.as_ptr() as *mut _) }.unwrap();
+    assert_eq!(address.as_str(), ADDRESS);
+
+    let invalid = CString::new("not an address").unwrap();
+    assert_invalid(unsafe { Address::from_abi(invalid.as_ptr() as *mut _) });
+    let pubkey = CString::new(PUBKEY).unwrap();
+    assert_invalid(unsafe { Address::from_abi(pubkey.as_ptr() as *mut _) });
+}
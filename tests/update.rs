@@ -0,0 +1,86 @@
+// 基于 MockLedger 校验未启用调用缓存时 update_text/update_int64 的读-改-写
+
+mod common;
+
+use jdchain_rust_contract::jdcc_api::LedgerService;
+use jdchain_rust_contract::jdcc_mock::MockLedger;
+use jdchain_rust_contract::jdcc_types::{ContractError, DataValue};
+
+fn latest(service: &LedgerService<&MockLedger>, key: &str) -> DataValue {
+    service.get_value(&common::account(), key, -1).unwrap().unwrap().value
+}
+
+#[test]
+fn missing_key_passes_none() {
+    let ledger = common::ledger();
+    let service = LedgerService::new(&ledger);
+    let account = common::account();
+
+    let mut seen = Some("unset");
+    assert_eq!(service.update_text(&account, "name", |old| {
+        seen = old.map(|_| "set");
+        "jd".to_string()
+    }).unwrap(), 0);
+    assert_eq!(seen, None);
+    assert_eq!(service.update_text(&account, "name", |old| format!("{}chain", old.unwrap())).unwrap(), 1);
+    assert_eq!(latest(&service, "name"), DataValue::Text("jdchain".to_string()));
+
+    let mut seen = Some(0);
+    assert_eq!(service.update_int64(&account, "count", |old| {
+        seen = old;
+        1
+    }).unwrap(), 0);
+    assert_eq!(seen, None);
+    assert_eq!(service.update_int64(&account, "count", |old| old.unwrap() + 41).unwrap(), 1);
+    assert_eq!(latest(&service, "count"), DataValue::Int64(42));
+}
+
+#[test]
+fn concurrent_write_conflicts() {
+    let ledger = common::ledger();
+    let service = LedgerService::new(&ledger);
+    let other = LedgerService::new(&ledger);
+    let account = common::account();
+    service.set_int64(&account, "count", 1).unwrap();
+
+    // 读写之间由其他调用写入新版本
+    let ret = service.update_int64(&account, "count", |old| {
+        other.set_int64(&account, "count", 10).unwrap();
+        old.unwrap() + 1
+    });
+    match ret {
+        Err(ContractError::VersionConflict { key, version }) => assert_eq!((key.as_str(), version), ("count", 0)),
+        other => panic!("unexpected result {:?}", other),
+    }
+    assert_eq!(latest(&service, "count"), DataValue::Int64(10));
+
+    // 数据不存在时读取版本为 -1
+    let ret = service.update_text(&account, "name", |_| {
+        other.set_text(&account, "name", "other").unwrap();
+        "mine".to_string()
+    });
+    match ret {
+        Err(ContractError::VersionConflict { key, version }) => assert_eq!((key.as_str(), version), ("name", -1)),
+        other => panic!("unexpected result {:?}", other),
+    }
+    assert_eq!(latest(&service, "name"), DataValue::Text("other".to_string()));
+}
+
+#[test]
+fn wrong_stored_type_is_decode_error() {
+    let ledger = common::ledger();
+    let service = LedgerService::new(&ledger);
+    let account = common::account();
+    service.set_text(&account, "name", "jd").unwrap();
+    service.set_int64(&account, "count", 1).unwrap();
+
+    let ret = service.update_int64(&account, "name", |_| panic!("closure called for text value"));
+    assert!(matches!(ret, Err(ContractError::Decode(_))), "{:?}", ret);
+    let ret = service.update_text(&account, "count", |_| panic!("closure called for int64 value"));
+    assert!(matches!(ret, Err(ContractError::Decode(_))), "{:?}", ret);
+
+    // 未写入新版本
+    let latest = service.get_value(&account, "name", -1).unwrap().unwrap();
+    assert_eq!((latest.value, latest.version), (DataValue::Text("jd".to_string()), 0));
+    assert_eq!(service.get_value_version(&account, "count").unwrap(), 0);
+}
@@ -0,0 +1,125 @@
+// 基于 MockLedger 校验各数据类型的写入与类型化读取，以及非法数值的拒绝
+
+mod common;
+
+use serde_json::json;
+
+use jdchain_rust_contract::jdcc_api::LedgerService;
+use jdchain_rust_contract::jdcc_types::{ContractError, DataValue, ValueType};
+
+use common::FixedHost;
+
+#[test]
+fn typed_values_round_trip() {
+    let ledger = common::ledger();
+    let service = LedgerService::new(&ledger);
+    let account = common::account();
+
+    let doc = json!({"name": "jd", "tags": ["a", "b"], "n": 1});
+    service.set_json(&account, "json", &doc).unwrap();
+    service.set_xml(&account, "xml", "<a x=\"1\">jd &amp; chain</a>").unwrap();
+    service.set_timestamp(&account, "ts", 1_700_000_000_123).unwrap();
+    service.set_image(&account, "img", b"\x89PNG\r\n\x1a\n\0").unwrap();
+    service.set_big_int(&account, "big", "-123456789012345678901234567890").unwrap();
+
+    let expected = [
+        ("json", DataValue::json(&doc).unwrap()),
+        ("xml", DataValue::Xml("<a x=\"1\">jd &amp; chain</a>".to_string())),
+        ("ts", DataValue::Timestamp(1_700_000_000_123)),
+        ("img", DataValue::Image(b"\x89PNG\r\n\x1a\n\0".to_vec())),
+        ("big", DataValue::BigInt("-123456789012345678901234567890".to_string())),
+    ];
+    for (key, value) in expected {
+        let data = service.get_value(&account, key, -1).unwrap().unwrap();
+        assert_eq!((data.key.as_str(), data.version), (key, 0));
+        assert_eq!(data.value, value);
+    }
+    let data = service.get_value(&account, "json", 0).unwrap().unwrap();
+    assert_eq!(data.value.to_json::<serde_json::Value>().unwrap(), doc);
+    assert_eq!(service.get_json::<serde_json::Value>(&account, "json", -1).unwrap().unwrap(), doc);
+}
+
+#[test]
+fn typed_getters_check_value_type() {
+    let ledger = common::ledger();
+    let service = LedgerService::new(&ledger);
+    let account = common::account();
+    service.set_text(&account, "text", "jd").unwrap();
+    service.set_int64(&account, "int", -7).unwrap();
+    service.set_xml(&account, "xml", "<a/>").unwrap();
+    service.set_timestamp(&account, "ts", 1_700_000_000_123).unwrap();
+    service.set_image(&account, "img", b"\0png").unwrap();
+    service.set_big_int(&account, "big", "123456789012345678901234567890").unwrap();
+
+    assert_eq!(service.get_text(&account, "text", -1).unwrap().as_deref(), Some("jd"));
+    assert_eq!(service.get_int64(&account, "int", 0).unwrap(), Some(-7));
+    assert_eq!(service.get_xml(&account, "xml", -1).unwrap().as_deref(), Some("<a/>"));
+    assert_eq!(service.get_timestamp(&account, "ts", -1).unwrap(), Some(1_700_000_000_123));
+    assert_eq!(service.get_image(&account, "img", -1).unwrap().as_deref(), Some(&b"\0png"[..]));
+    assert_eq!(service.get_big_int(&account, "big", -1).unwrap().as_deref(), Some("123456789012345678901234567890"));
+    assert_eq!(service.get_text(&account, "missing", -1).unwrap(), None);
+    assert_eq!(service.get_int64(&account, "int", 1).unwrap(), None);
+
+    // 同为文本或数值的其它类型不做隐式转换
+    assert!(matches!(service.get_text(&account, "xml", -1), Err(ContractError::Decode(_))));
+    assert!(matches!(service.get_int64(&account, "ts", -1), Err(ContractError::Decode(_))));
+    assert!(matches!(service.get_big_int(&account, "text", -1), Err(ContractError::Decode(_))));
+    assert!(matches!(service.get_image(&account, "text", -1), Err(ContractError::Decode(_))));
+}
+
+#[test]
+fn big_int_rejects_non_decimal_text() {
+    for value in ["", "-", "1a", "+1", "1.0", " 1", "--1"] {
+        assert!(matches!(DataValue::big_int(value), Err(ContractError::Encode(_))), "{:?}", value);
+    }
+    assert_eq!(DataValue::big_int("-0").unwrap(), DataValue::BigInt("-0".to_string()));
+    assert_eq!(DataValue::big_int("0012").unwrap().value_type(), ValueType::BigInt);
+
+    let ledger = common::ledger();
+    let service = LedgerService::new(&ledger);
+    let account = common::account();
+    assert!(matches!(service.set_big_int(&account, "big", "1a"), Err(ContractError::Encode(_))));
+    assert_eq!(service.get_value_version(&account, "big").unwrap(), -1);
+
+    // 绕过构造方法的非法值由宿主拒绝
+    assert!(service.set_value(&account, "big", DataValue::BigInt("1a".to_string())).is_err());
+    assert_eq!(service.get_value_version(&account, "big").unwrap(), -1);
+}
+
+#[test]
+fn non_decimal_big_int_response_is_rejected() {
+    let account = common::account();
+    for v in ["1a", "", "-", "1e9"] {
+        let host = FixedHost(format!(r#"{{"rc":0,"k":"big","t":"BIG_INT","v":"{}","ver":0}}"#, v).into_bytes());
+        match LedgerService::new(&host).get_big_int(&account, "big", -1) {
+            Err(ContractError::Decode(_)) => {}
+            other => panic!("unexpected result {:?} for {:?}", other, v),
+        }
+    }
+
+    let host = FixedHost(br#"{"rc":0,"k":"big","t":"BIG_INT","v":"-99999999999999999999","ver":1}"#.to_vec());
+    assert_eq!(LedgerService::new(&host).get_big_int(&account, "big", -1).unwrap().as_deref(), Some("-99999999999999999999"));
+}
+
+#[test]
+fn non_numeric_int64_response_is_rejected() {
+    let account = common::account();
+    for t in ["INT64", "TIMESTAMP"] {
+        let host = FixedHost(format!(r#"{{"rc":0,"k":"count","t":"{}","v":"x","ver":0}}"#, t).into_bytes());
+        match LedgerService::new(&host).get_value(&account, "count", -1) {
+            Err(ContractError::Decode(_)) => {}
+            other => panic!("unexpected result {:?}", other.map(|data| data.map(|data| data.value))),
+        }
+    }
+
+    let host = FixedHost(br#"{"rc":0,"k":"count","t":"INT64","v":"-42","ver":3}"#.to_vec());
+    let data = LedgerService::new(&host).get_value(&account, "count", -1).unwrap().unwrap();
+    assert_eq!((data.value, data.version), (DataValue::Int64(-42), 3));
+
+    // MockLedger 同样拒绝写入非法数值
+    let ledger = common::ledger();
+    let resp = ledger.handle(format!(r#"{{"rt":20,"a":"{}","k":"count","t":"INT64","v":"x"}}"#, account).as_bytes());
+    let resp = String::from_utf8(resp).unwrap();
+    assert!(!resp.contains(r#""rc":0"#), "{}", resp);
+    assert_eq!(LedgerService::new(&ledger).get_value_version(&account, "count").unwrap(), -1);
+}